@@ -0,0 +1,58 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn auto_size() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    // Window is sized by its content, and text is wrapped in the max width.
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        max_width: Some(800),
+        min_height: Some(300),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        justify_content: style::JustifyContent::Center,
+        padding: style::Padding(40, 40, 40, 40),
+        gap: 20,
+        ..style::WindowStyle::default()
+    })?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    writer.set_text(
+        text,
+        style::Style {
+            line_height: 1.8,
+            font_size: 60.,
+            color: style::Rgba([255, 255, 255, 255]),
+            ..style::Style::default()
+        },
+        Some(font.clone()),
+    )?;
+
+    let mut author = OGImageWriter::new(style::WindowStyle {
+        align_items: style::AlignItems::Center,
+        flex_direction: style::FlexDirection::Row,
+        gap: 20,
+        ..style::WindowStyle::default()
+    })?;
+    author.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        60,
+        60,
+        ImageInputFormat::Png,
+        style::Style {
+            border_radius: style::BorderRadius(30, 30, 30, 30),
+            ..style::Style::default()
+        },
+    )?;
+    author.set_text(
+        "Author",
+        style::Style {
+            font_size: 30.,
+            color: style::Rgba([255, 180, 60, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+    writer.set_container(&mut author, style::Style::default())?;
+
+    Ok(writer)
+}
@@ -0,0 +1,23 @@
+use og_image_writer::{style, writer::OGImageWriter, Error};
+
+pub fn auto_size_text() -> Result<OGImageWriter, Error> {
+    // Window is sized by text without padding, and descenders are not clipped.
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        ..style::WindowStyle::default()
+    })?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Roboto-Light.ttf") as &[u8]);
+
+    writer.set_text(
+        "Hello gjpqy World",
+        style::Style {
+            font_size: 60.,
+            color: style::Rgba([255, 255, 255, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    Ok(writer)
+}
@@ -0,0 +1,65 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn bleed() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        ..style::WindowStyle::default()
+    })?;
+
+    // Decorative circles bleed off the edges of the window.
+    for (top, left, size) in [(-120, -80, 320), (330, 840, 260)] {
+        let mut circle = OGImageWriter::new(style::WindowStyle {
+            width: Some(size),
+            height: Some(size),
+            background_color: Some(style::Rgba([255, 180, 60, 255])),
+            ..style::WindowStyle::default()
+        })?;
+        writer.set_container(
+            &mut circle,
+            style::Style {
+                position: style::Position::Absolute,
+                top: Some(top),
+                left: Some(left),
+                border_radius: style::BorderRadius(size / 2, size / 2, size / 2, size / 2),
+                ..style::Style::default()
+            },
+        )?;
+    }
+
+    writer.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        200,
+        200,
+        ImageInputFormat::Png,
+        style::Style {
+            position: style::Position::Absolute,
+            top: Some(-60),
+            right: Some(-60),
+            border_radius: style::BorderRadius(100, 100, 100, 100),
+            ..style::Style::default()
+        },
+    )?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    writer.set_text(
+        text,
+        style::Style {
+            margin: style::Margin(0, 100, 0, 100),
+            line_height: 1.8,
+            font_size: 50.,
+            color: style::Rgba([255, 255, 255, 255]),
+            text_align: style::TextAlign::Center,
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    Ok(writer)
+}
@@ -0,0 +1,100 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn border() -> Result<OGImageWriter, Error> {
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    let mut card = OGImageWriter::new(style::WindowStyle {
+        align_items: style::AlignItems::Center,
+        flex_direction: style::FlexDirection::Row,
+        gap: 40,
+        ..style::WindowStyle::default()
+    })?;
+
+    // Image is clipped inside the border.
+    card.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        160,
+        160,
+        ImageInputFormat::Png,
+        style::Style {
+            padding: style::Padding(6, 6, 6, 6),
+            border_radius: style::BorderRadius(86, 86, 86, 86),
+            background_color: Some(style::Rgba([255, 255, 255, 255])),
+            border_width: 6,
+            border_color: style::Rgba([255, 180, 60, 255]),
+            ..style::Style::default()
+        },
+    )?;
+
+    let mut callout = OGImageWriter::new(style::WindowStyle {
+        gap: 20,
+        ..style::WindowStyle::default()
+    })?;
+
+    callout.set_text(
+        "NEW",
+        style::Style {
+            padding: style::Padding(2, 20, 10, 20),
+            border_radius: style::BorderRadius(20, 20, 20, 20),
+            background_color: Some(style::Rgba([255, 80, 120, 255])),
+            font_size: 30.,
+            color: style::Rgba([255, 255, 255, 255]),
+            ..style::Style::default()
+        },
+        Some(font.clone()),
+    )?;
+
+    callout.set_text(
+        "Open Graphic Image Writer",
+        style::Style {
+            padding: style::Padding(6, 20, 17, 20),
+            border_radius: style::BorderRadius(12, 12, 12, 12),
+            border_width: 4,
+            border_color: style::Rgba([255, 255, 255, 255]),
+            border_style: style::BorderStyle::Dashed,
+            font_size: 40.,
+            color: style::Rgba([255, 255, 255, 255]),
+            ..style::Style::default()
+        },
+        Some(font.clone()),
+    )?;
+
+    callout.set_text(
+        "for Web Developer",
+        style::Style {
+            padding: style::Padding(6, 20, 17, 20),
+            border_width: 4,
+            border_color: style::Rgba([255, 180, 60, 255]),
+            border_style: style::BorderStyle::Dotted,
+            font_size: 40.,
+            color: style::Rgba([255, 180, 60, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    card.set_container(&mut callout, style::Style::default())?;
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        ..style::WindowStyle::default()
+    })?;
+
+    writer.set_container(
+        &mut card,
+        style::Style {
+            padding: style::Padding(40, 40, 40, 40),
+            border_radius: style::BorderRadius(30, 30, 30, 30),
+            background_color: Some(style::Rgba([100, 60, 130, 255])),
+            border_width: 3,
+            border_color: style::Rgba([255, 255, 255, 120]),
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
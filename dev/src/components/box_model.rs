@@ -0,0 +1,63 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn box_model() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    let mut card = OGImageWriter::new(style::WindowStyle {
+        background_color: Some(style::Rgba([255, 255, 255, 255])),
+        align_items: style::AlignItems::Center,
+        flex_direction: style::FlexDirection::Row,
+        gap: 30,
+        ..style::WindowStyle::default()
+    })?;
+
+    card.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        100,
+        100,
+        ImageInputFormat::Png,
+        style::Style {
+            width: Some(120),
+            height: Some(120),
+            ..style::Style::default()
+        },
+    )?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    // Text is broken into lines in the content box.
+    card.set_text(
+        text,
+        style::Style {
+            padding: style::Padding(10, 20, 10, 20),
+            width: Some(500),
+            min_height: Some(200),
+            box_sizing: style::BoxSizing::BorderBox,
+            line_height: 1.8,
+            font_size: 50.,
+            color: style::Rgba([70, 40, 90, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        ..style::WindowStyle::default()
+    })?;
+
+    writer.set_container(
+        &mut card,
+        style::Style {
+            padding: style::Padding(30, 40, 30, 40),
+            border_radius: style::BorderRadius(20, 20, 20, 20),
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
@@ -0,0 +1,80 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn box_shadow() -> Result<OGImageWriter, Error> {
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    let mut card = OGImageWriter::new(style::WindowStyle {
+        align_items: style::AlignItems::Center,
+        flex_direction: style::FlexDirection::Row,
+        gap: 40,
+        ..style::WindowStyle::default()
+    })?;
+
+    card.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        160,
+        160,
+        ImageInputFormat::Png,
+        style::Style {
+            border_radius: style::BorderRadius(80, 80, 80, 80),
+            box_shadow: Some(style::BoxShadow {
+                offset_y: 6,
+                blur: 16,
+                color: style::Rgba([0, 0, 0, 120]),
+                ..style::BoxShadow::default()
+            }),
+            ..style::Style::default()
+        },
+    )?;
+
+    // Inset shadow makes text box look like pressed.
+    card.set_text(
+        "Open Graphic\nImage Writer",
+        style::Style {
+            padding: style::Padding(14, 30, 26, 30),
+            border_radius: style::BorderRadius(16, 16, 16, 16),
+            background_color: Some(style::Rgba([240, 236, 245, 255])),
+            box_shadow: Some(style::BoxShadow {
+                offset_x: 4,
+                offset_y: 4,
+                blur: 12,
+                color: style::Rgba([70, 40, 90, 100]),
+                inset: true,
+                ..style::BoxShadow::default()
+            }),
+            white_space: style::WhiteSpace::PreLine,
+            font_size: 50.,
+            color: style::Rgba([70, 40, 90, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([230, 225, 240, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        ..style::WindowStyle::default()
+    })?;
+
+    writer.set_container(
+        &mut card,
+        style::Style {
+            padding: style::Padding(50, 50, 50, 50),
+            border_radius: style::BorderRadius(30, 30, 30, 30),
+            background_color: Some(style::Rgba([255, 255, 255, 255])),
+            box_shadow: Some(style::BoxShadow {
+                offset_y: 20,
+                blur: 40,
+                spread: -5,
+                color: style::Rgba([70, 40, 90, 90]),
+                ..style::BoxShadow::default()
+            }),
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
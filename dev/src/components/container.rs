@@ -3,11 +3,11 @@ use og_image_writer::{font_context::FontContext, style, writer::OGImageWriter, E
 pub fn container() -> Result<OGImageWriter, Error> {
     let text = "This is Open Graphic Image Writer for Web Developer.";
 
-    let mut font_context = FontContext::new();
+    let font_context = FontContext::new();
 
     let mut container = OGImageWriter::new(style::WindowStyle {
-        width: 500,
-        height: 250,
+        width: Some(500),
+        height: Some(250),
         background_color: Some(style::Rgba([70, 40, 90, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
@@ -17,6 +17,7 @@ pub fn container() -> Result<OGImageWriter, Error> {
     font_context.push(Vec::from(
         include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]
     ))?;
+    container.set_font_context(&font_context);
 
     container.set_text(
         text,
@@ -35,13 +36,14 @@ pub fn container() -> Result<OGImageWriter, Error> {
     )?;
 
     let mut writer = OGImageWriter::new(style::WindowStyle {
-        width: 1024,
-        height: 512,
+        width: Some(1024),
+        height: Some(512),
         background_color: Some(style::Rgba([255, 255, 255, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
         ..style::WindowStyle::default()
     })?;
+    writer.set_font_context(&font_context);
 
     writer.set_container(
         &mut container,
@@ -4,8 +4,8 @@ pub fn ellipsis() -> Result<OGImageWriter, Error> {
     let text = "This is Open Graphic Image Writer for Web Developer. This is multi line text, but this text is omitted with ellipsis.";
 
     let mut writer = OGImageWriter::new(style::WindowStyle {
-        width: 1024,
-        height: 512,
+        width: Some(1024),
+        height: Some(512),
         background_color: Some(style::Rgba([70, 40, 90, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
@@ -6,8 +6,8 @@ pub fn encode() -> Result<Vec<u8>, Error> {
     let height = 512;
 
     let mut writer = OGImageWriter::new(style::WindowStyle {
-        width,
-        height,
+        width: Some(width),
+        height: Some(height),
         background_color: Some(style::Rgba([70, 40, 90, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
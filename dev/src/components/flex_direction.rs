@@ -0,0 +1,76 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn flex_direction() -> Result<OGImageWriter, Error> {
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::SpaceEvenly,
+        flex_direction: style::FlexDirection::Row,
+        ..style::WindowStyle::default()
+    })?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    let columns = [
+        (
+            style::FlexDirection::ColumnReverse,
+            style::JustifyContent::Start,
+        ),
+        (
+            style::FlexDirection::ColumnReverse,
+            style::JustifyContent::End,
+        ),
+    ];
+    for (flex_direction, justify_content) in columns {
+        let mut column = OGImageWriter::new(style::WindowStyle {
+            width: Some(200),
+            height: Some(440),
+            background_color: Some(style::Rgba([255, 255, 255, 40])),
+            align_items: style::AlignItems::Center,
+            justify_content,
+            flex_direction,
+            gap: 10,
+            ..style::WindowStyle::default()
+        })?;
+        for label in ["1", "2", "3"] {
+            column.set_text(
+                label,
+                style::Style {
+                    font_size: 60.,
+                    color: style::Rgba([255, 255, 255, 255]),
+                    ..style::Style::default()
+                },
+                Some(font.clone()),
+            )?;
+        }
+        writer.set_container(&mut column, style::Style::default())?;
+    }
+
+    // Images are placed from the right, so the first image is on the right.
+    let mut row = OGImageWriter::new(style::WindowStyle {
+        width: Some(440),
+        height: Some(200),
+        background_color: Some(style::Rgba([255, 255, 255, 40])),
+        align_items: style::AlignItems::Center,
+        flex_direction: style::FlexDirection::RowReverse,
+        gap: 10,
+        ..style::WindowStyle::default()
+    })?;
+    for size in [120, 80, 40] {
+        row.set_img_with_data(
+            include_bytes!("../../../assets/thumbnail_circle.png"),
+            size,
+            size,
+            ImageInputFormat::Png,
+            style::Style {
+                border_radius: style::BorderRadius(size / 2, size / 2, size / 2, size / 2),
+                ..style::Style::default()
+            },
+        )?;
+    }
+    writer.set_container(&mut row, style::Style::default())?;
+
+    Ok(writer)
+}
@@ -0,0 +1,58 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn flex_grow() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        flex_direction: style::FlexDirection::Row,
+        ..style::WindowStyle::default()
+    })?;
+
+    writer.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        150,
+        150,
+        ImageInputFormat::Png,
+        style::Style {
+            margin: style::Margin(0, 20, 0, 40),
+            border_radius: style::BorderRadius(75, 75, 75, 75),
+            ..style::Style::default()
+        },
+    )?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    // Title takes the rest of the row, and is broken into lines in that width.
+    writer.set_text(
+        text,
+        style::Style {
+            margin: style::Margin(0, 20, 0, 20),
+            line_height: 1.8,
+            font_size: 60.,
+            color: style::Rgba([255, 255, 255, 255]),
+            flex_grow: 1.,
+            flex_basis: Some(0),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    writer.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        80,
+        80,
+        ImageInputFormat::Png,
+        style::Style {
+            margin: style::Margin(0, 40, 40, 20),
+            border_radius: style::BorderRadius(40, 40, 40, 40),
+            align_self: Some(style::AlignItems::End),
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
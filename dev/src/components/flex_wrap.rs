@@ -0,0 +1,56 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn flex_wrap() -> Result<OGImageWriter, Error> {
+    let tags = [
+        "#rust",
+        "#wasm",
+        "#og-image",
+        "#flexbox",
+        "#typography",
+        "#web",
+        "#design",
+    ];
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        flex_direction: style::FlexDirection::Row,
+        flex_wrap: style::FlexWrap::Wrap,
+        align_content: style::AlignContent::Center,
+        ..style::WindowStyle::default()
+    })?;
+
+    for _ in 0..4 {
+        writer.set_img_with_data(
+            include_bytes!("../../../assets/thumbnail_circle.png"),
+            100,
+            100,
+            ImageInputFormat::Png,
+            style::Style {
+                margin: style::Margin(10, 10, 10, 10),
+                border_radius: style::BorderRadius(50, 50, 50, 50),
+                ..style::Style::default()
+            },
+        )?;
+    }
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    for tag in tags {
+        writer.set_text(
+            tag,
+            style::Style {
+                margin: style::Margin(10, 20, 10, 20),
+                font_size: 50.,
+                color: style::Rgba([255, 255, 255, 255]),
+                ..style::Style::default()
+            },
+            Some(font.clone()),
+        )?;
+    }
+
+    Ok(writer)
+}
@@ -2,8 +2,8 @@ use og_image_writer::{font_context::FontContext, style, writer::OGImageWriter, E
 
 pub fn font_context() -> Result<OGImageWriter, Error> {
     let mut writer = OGImageWriter::new(style::WindowStyle {
-        width: 1024,
-        height: 512,
+        width: Some(1024),
+        height: Some(512),
         background_color: Some(style::Rgba([70, 40, 90, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
@@ -11,7 +11,7 @@ pub fn font_context() -> Result<OGImageWriter, Error> {
     })?;
 
     // Set global font data
-    let mut fc = FontContext::new();
+    let fc = FontContext::new();
     fc.push(Vec::from(
         include_bytes!("../../../fonts/OpenSansCondensed-Light.ttf") as &[u8],
     ))?;
@@ -21,6 +21,7 @@ pub fn font_context() -> Result<OGImageWriter, Error> {
     fc.push(Vec::from(
         include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]
     ))?;
+    writer.set_font_context(&fc);
 
     let mut textarea = TextArea::new();
     textarea.push_text("こんにちは。 ");
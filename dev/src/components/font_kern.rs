@@ -4,8 +4,8 @@ pub fn font_kern() -> Result<OGImageWriter, Error> {
     let text = "これは、画像を動的に作るためのツールです。";
 
     let mut writer = OGImageWriter::new(style::WindowStyle {
-        width: 1024,
-        height: 512,
+        width: Some(1024),
+        height: Some(512),
         background_color: Some(style::Rgba([70, 40, 90, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
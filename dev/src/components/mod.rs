@@ -1,25 +1,53 @@
 mod absolute;
+mod auto_size;
+mod auto_size_text;
 mod background_color;
 mod background_image;
+mod bleed;
+mod border;
+mod box_model;
+mod box_shadow;
 mod container;
 mod ellipsis;
 mod encode;
+mod flex_direction;
+mod flex_grow;
+mod flex_wrap;
 mod font_context;
 mod font_kern;
 mod into_vec;
+mod object_fit;
+mod overflow;
 mod row_container;
+mod space_between;
 mod textarea;
 mod white_space;
+mod window_padding;
+mod z_index;
 
 pub use absolute::*;
+pub use auto_size::*;
+pub use auto_size_text::*;
 pub use background_color::*;
 pub use background_image::*;
+pub use bleed::*;
+pub use border::*;
+pub use box_model::*;
+pub use box_shadow::*;
 pub use container::*;
 pub use ellipsis::*;
 pub use encode::*;
+pub use flex_direction::*;
+pub use flex_grow::*;
+pub use flex_wrap::*;
 pub use font_context::*;
 pub use font_kern::*;
 pub use into_vec::*;
+pub use object_fit::*;
+pub use overflow::*;
 pub use row_container::*;
+pub use space_between::*;
 pub use textarea::*;
 pub use white_space::*;
+pub use window_padding::*;
+pub use z_index::*;
@@ -0,0 +1,44 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn object_fit() -> Result<OGImageWriter, Error> {
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        flex_direction: style::FlexDirection::Row,
+        gap: 24,
+        ..style::WindowStyle::default()
+    })?;
+
+    // Square image is fitted in tall box.
+    let fits = [
+        (style::ObjectFit::Contain, style::ObjectPosition::default()),
+        (style::ObjectFit::Cover, style::ObjectPosition::default()),
+        (style::ObjectFit::Cover, style::ObjectPosition(0., 0.5)),
+        (style::ObjectFit::Fill, style::ObjectPosition::default()),
+        (
+            style::ObjectFit::ScaleDown,
+            style::ObjectPosition::default(),
+        ),
+        (style::ObjectFit::None, style::ObjectPosition::default()),
+    ];
+    for (object_fit, object_position) in fits {
+        writer.set_img_with_data(
+            include_bytes!("../../../assets/thumbnail_circle.png"),
+            140,
+            260,
+            ImageInputFormat::Png,
+            style::Style {
+                object_fit: Some(object_fit),
+                object_position,
+                background_color: Some(style::Rgba([255, 255, 255, 255])),
+                border_radius: style::BorderRadius(16, 16, 16, 16),
+                ..style::Style::default()
+            },
+        )?;
+    }
+
+    Ok(writer)
+}
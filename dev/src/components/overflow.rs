@@ -0,0 +1,67 @@
+use og_image_writer::{style, writer::OGImageWriter, Error};
+
+pub fn overflow() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    let mut card = OGImageWriter::new(style::WindowStyle {
+        width: Some(700),
+        height: Some(320),
+        background_color: Some(style::Rgba([255, 255, 255, 255])),
+        justify_content: style::JustifyContent::Center,
+        overflow: style::Overflow::Hidden,
+        ..style::WindowStyle::default()
+    })?;
+
+    // Decorative circles are clipped by the rounded card.
+    for (top, left, size) in [(-140, -100, 300), (200, 560, 240)] {
+        let mut circle = OGImageWriter::new(style::WindowStyle {
+            width: Some(size),
+            height: Some(size),
+            background_color: Some(style::Rgba([255, 180, 60, 255])),
+            ..style::WindowStyle::default()
+        })?;
+        card.set_container(
+            &mut circle,
+            style::Style {
+                position: style::Position::Absolute,
+                top: Some(top),
+                left: Some(left),
+                border_radius: style::BorderRadius(size / 2, size / 2, size / 2, size / 2),
+                ..style::Style::default()
+            },
+        )?;
+    }
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    card.set_text(
+        text,
+        style::Style {
+            margin: style::Margin(0, 60, 0, 60),
+            line_height: 1.8,
+            font_size: 50.,
+            color: style::Rgba([70, 40, 90, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        ..style::WindowStyle::default()
+    })?;
+
+    writer.set_container(
+        &mut card,
+        style::Style {
+            border_radius: style::BorderRadius(40, 40, 40, 40),
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
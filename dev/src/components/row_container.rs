@@ -4,8 +4,8 @@ pub fn row_container() -> Result<OGImageWriter, Error> {
     let text = "This is Open Graphic Image Writer for Web Developer.";
 
     let mut container = OGImageWriter::new(style::WindowStyle {
-        width: 500,
-        height: 250,
+        width: Some(500),
+        height: Some(250),
         background_color: Some(style::Rgba([70, 40, 90, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
@@ -31,12 +31,13 @@ pub fn row_container() -> Result<OGImageWriter, Error> {
     )?;
 
     let mut writer = OGImageWriter::new(style::WindowStyle {
-        width: 1024,
-        height: 512,
+        width: Some(1024),
+        height: Some(512),
         background_color: Some(style::Rgba([255, 255, 255, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
         flex_direction: style::FlexDirection::Row,
+        ..style::WindowStyle::default()
     })?;
 
     writer.set_container(
@@ -60,7 +61,7 @@ pub fn row_container() -> Result<OGImageWriter, Error> {
             word_break: style::WordBreak::Normal,
             color: style::Rgba([0, 0, 0, 255]),
             text_align: style::TextAlign::Center,
-            max_width: Some(500),
+            max_width: Some(460),
             max_height: Some(400),
             ..style::Style::default()
         },
@@ -0,0 +1,69 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn space_between() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Start,
+        justify_content: style::JustifyContent::SpaceBetween,
+        ..style::WindowStyle::default()
+    })?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    writer.set_text(
+        text,
+        style::Style {
+            margin: style::Margin(40, 40, 0, 40),
+            line_height: 1.8,
+            font_size: 80.,
+            color: style::Rgba([255, 255, 255, 255]),
+            max_height: Some(300),
+            text_overflow: style::TextOverflow::Ellipsis,
+            ..style::Style::default()
+        },
+        Some(font.clone()),
+    )?;
+
+    // Footer is sized by its content.
+    let mut footer = OGImageWriter::new(style::WindowStyle {
+        align_items: style::AlignItems::Center,
+        flex_direction: style::FlexDirection::Row,
+        gap: 20,
+        ..style::WindowStyle::default()
+    })?;
+
+    footer.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        80,
+        80,
+        ImageInputFormat::Png,
+        style::Style {
+            border_radius: style::BorderRadius(40, 40, 40, 40),
+            ..style::Style::default()
+        },
+    )?;
+
+    footer.set_text(
+        "Author",
+        style::Style {
+            font_size: 40.,
+            color: style::Rgba([255, 255, 255, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    writer.set_container(
+        &mut footer,
+        style::Style {
+            margin: style::Margin(0, 40, 40, 40),
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
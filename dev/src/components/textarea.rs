@@ -2,8 +2,8 @@ use og_image_writer::{style, writer::OGImageWriter, Error, TextArea};
 
 pub fn textarea() -> Result<OGImageWriter, Error> {
     let mut writer = OGImageWriter::new(style::WindowStyle {
-        width: 1024,
-        height: 512,
+        width: Some(1024),
+        height: Some(512),
         background_color: Some(style::Rgba([70, 40, 90, 255])),
         align_items: style::AlignItems::Center,
         justify_content: style::JustifyContent::Center,
@@ -0,0 +1,74 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn window_padding() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        justify_content: style::JustifyContent::SpaceBetween,
+        padding: style::Padding(60, 80, 60, 80),
+        ..style::WindowStyle::default()
+    })?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    // Text is wrapped inside the window padding.
+    writer.set_text(
+        text,
+        style::Style {
+            line_height: 1.8,
+            font_size: 50.,
+            color: style::Rgba([255, 255, 255, 255]),
+            ..style::Style::default()
+        },
+        Some(font.clone()),
+    )?;
+
+    writer.set_text(
+        "og_image_writer",
+        style::Style {
+            padding: style::Padding(0, 0, 10, 0),
+            font_size: 30.,
+            color: style::Rgba([255, 180, 60, 255]),
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    // Absolute element is placed inside the window padding.
+    writer.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        100,
+        100,
+        ImageInputFormat::Png,
+        style::Style {
+            position: style::Position::Absolute,
+            bottom: Some(0),
+            right: Some(0),
+            border_radius: style::BorderRadius(50, 50, 50, 50),
+            ..style::Style::default()
+        },
+    )?;
+
+    // Absolute element that ignores the window padding bleeds to the edge.
+    let mut bar = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(16),
+        background_color: Some(style::Rgba([255, 180, 60, 255])),
+        ..style::WindowStyle::default()
+    })?;
+    writer.set_container(
+        &mut bar,
+        style::Style {
+            position: style::Position::Absolute,
+            bottom: Some(0),
+            left: Some(0),
+            ignore_window_padding: true,
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
@@ -0,0 +1,82 @@
+use og_image_writer::{img::ImageInputFormat, style, writer::OGImageWriter, Error};
+
+pub fn z_index() -> Result<OGImageWriter, Error> {
+    let text = "This is Open Graphic Image Writer for Web Developer.";
+
+    let mut writer = OGImageWriter::new(style::WindowStyle {
+        width: Some(1024),
+        height: Some(512),
+        background_color: Some(style::Rgba([70, 40, 90, 255])),
+        align_items: style::AlignItems::Center,
+        justify_content: style::JustifyContent::Center,
+        ..style::WindowStyle::default()
+    })?;
+
+    let font = Vec::from(include_bytes!("../../../fonts/Mplus1-Black.ttf") as &[u8]);
+
+    writer.set_text(
+        text,
+        style::Style {
+            margin: style::Margin(0, 100, 0, 100),
+            line_height: 1.8,
+            font_size: 50.,
+            color: style::Rgba([255, 255, 255, 255]),
+            text_align: style::TextAlign::Center,
+            ..style::Style::default()
+        },
+        Some(font.clone()),
+    )?;
+
+    // Decoration is set after text, but it is painted behind text.
+    let mut circle = OGImageWriter::new(style::WindowStyle {
+        width: Some(360),
+        height: Some(360),
+        background_color: Some(style::Rgba([255, 180, 60, 255])),
+        ..style::WindowStyle::default()
+    })?;
+    writer.set_container(
+        &mut circle,
+        style::Style {
+            position: style::Position::Absolute,
+            top: Some(76),
+            left: Some(332),
+            border_radius: style::BorderRadius(180, 180, 180, 180),
+            z_index: -1,
+            ..style::Style::default()
+        },
+    )?;
+
+    // Overlay is painted over everything.
+    writer.set_text(
+        "NEW",
+        style::Style {
+            padding: style::Padding(2, 20, 10, 20),
+            border_radius: style::BorderRadius(20, 20, 20, 20),
+            background_color: Some(style::Rgba([255, 80, 120, 255])),
+            font_size: 30.,
+            color: style::Rgba([255, 255, 255, 255]),
+            position: style::Position::Absolute,
+            top: Some(150),
+            left: Some(600),
+            z_index: 1,
+            ..style::Style::default()
+        },
+        Some(font),
+    )?;
+
+    writer.set_img_with_data(
+        include_bytes!("../../../assets/thumbnail_circle.png"),
+        120,
+        120,
+        ImageInputFormat::Png,
+        style::Style {
+            position: style::Position::Absolute,
+            top: Some(100),
+            left: Some(540),
+            border_radius: style::BorderRadius(60, 60, 60, 60),
+            ..style::Style::default()
+        },
+    )?;
+
+    Ok(writer)
+}
@@ -30,6 +30,9 @@ fn assert_image(expected: &[u8], actual: RgbaImage) {
 macro_rules! assert_component {
     ($component_name:ident) => {{
         let mut w = $component_name().unwrap();
+        // Layout by taffy must be the same as the built-in layout.
+        #[cfg(feature = "taffy")]
+        w.set_layout_engine(og_image_writer::LayoutEngine::Taffy);
         w.paint().unwrap();
         let rgba = w.into_rgba().unwrap();
         let path = format!(
@@ -46,6 +49,16 @@ fn compare_absolute() {
     assert_component!(absolute);
 }
 
+#[test]
+fn compare_auto_size() {
+    assert_component!(auto_size);
+}
+
+#[test]
+fn compare_auto_size_text() {
+    assert_component!(auto_size_text);
+}
+
 #[test]
 fn compare_background_color() {
     assert_component!(background_color);
@@ -56,6 +69,26 @@ fn compare_background_image() {
     assert_component!(background_image);
 }
 
+#[test]
+fn compare_bleed() {
+    assert_component!(bleed);
+}
+
+#[test]
+fn compare_border() {
+    assert_component!(border);
+}
+
+#[test]
+fn compare_box_model() {
+    assert_component!(box_model);
+}
+
+#[test]
+fn compare_box_shadow() {
+    assert_component!(box_shadow);
+}
+
 #[test]
 fn compare_container() {
     assert_component!(container);
@@ -66,6 +99,21 @@ fn compare_ellipsis() {
     assert_component!(ellipsis);
 }
 
+#[test]
+fn compare_flex_direction() {
+    assert_component!(flex_direction);
+}
+
+#[test]
+fn compare_flex_grow() {
+    assert_component!(flex_grow);
+}
+
+#[test]
+fn compare_flex_wrap() {
+    assert_component!(flex_wrap);
+}
+
 #[test]
 fn compare_font_context() {
     assert_component!(font_context);
@@ -75,11 +123,27 @@ fn compare_font_context() {
 fn compare_font_kern() {
     assert_component!(font_kern);
 }
+
+#[test]
+fn compare_object_fit() {
+    assert_component!(object_fit);
+}
+
+#[test]
+fn compare_overflow() {
+    assert_component!(overflow);
+}
+
 #[test]
 fn compare_row_container() {
     assert_component!(row_container);
 }
 
+#[test]
+fn compare_space_between() {
+    assert_component!(space_between);
+}
+
 #[test]
 fn compare_textarea() {
     assert_component!(textarea);
@@ -89,3 +153,13 @@ fn compare_textarea() {
 fn compare_white_space() {
     assert_component!(white_space);
 }
+
+#[test]
+fn compare_window_padding() {
+    assert_component!(window_padding);
+}
+
+#[test]
+fn compare_z_index() {
+    assert_component!(z_index);
+}
@@ -17,13 +17,27 @@ macro_rules! snapshot {
 #[ignore]
 fn update_snapshots() {
     snapshot!(absolute);
+    snapshot!(auto_size);
+    snapshot!(auto_size_text);
     snapshot!(background_color);
     snapshot!(background_image);
+    snapshot!(bleed);
+    snapshot!(border);
+    snapshot!(box_model);
+    snapshot!(box_shadow);
     snapshot!(container);
     snapshot!(ellipsis);
+    snapshot!(flex_direction);
+    snapshot!(flex_grow);
+    snapshot!(flex_wrap);
     snapshot!(font_context);
     snapshot!(font_kern);
+    snapshot!(object_fit);
+    snapshot!(overflow);
     snapshot!(row_container);
+    snapshot!(space_between);
     snapshot!(textarea);
     snapshot!(white_space);
+    snapshot!(window_padding);
+    snapshot!(z_index);
 }
@@ -151,11 +151,10 @@ export const drawImg = (writer: Writer, w: number, h: number) => {
   const imgWriter = OGImageWriter.new(windowStyle);
 
   const fontContext = FontContext.new();
-  // clear global memory cache
-  fontContext.clear();
   for (const font of writer.fontContext.context) {
     fontContext.push(font);
   }
+  imgWriter.set_font_context(fontContext);
 
   for (const elm of writer.data) {
     switch (elm.type) {
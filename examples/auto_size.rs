@@ -0,0 +1,13 @@
+use dev::components::auto_size;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = auto_size()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_auto_size.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use dev::components::bleed;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = bleed()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_bleed.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use dev::components::border;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = border()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_border.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
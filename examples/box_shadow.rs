@@ -0,0 +1,13 @@
+use dev::components::box_shadow;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = box_shadow()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_box_shadow.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use dev::components::flex_direction;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = flex_direction()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_flex_direction.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
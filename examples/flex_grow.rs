@@ -0,0 +1,13 @@
+use dev::components::flex_grow;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = flex_grow()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_flex_grow.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use dev::components::object_fit;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = object_fit()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_object_fit.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
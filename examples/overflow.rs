@@ -0,0 +1,13 @@
+use dev::components::overflow;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = overflow()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_overflow.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use dev::components::space_between;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = space_between()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_space_between.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
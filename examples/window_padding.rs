@@ -0,0 +1,13 @@
+use dev::components::window_padding;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = window_padding()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_window_padding.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use dev::components::z_index;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let mut w = z_index()?;
+
+    let out_dir = "./examples";
+    let out_filename = "output_z_index.png";
+
+    w.generate(Path::new(&format!("{}/{}", out_dir, out_filename)))?;
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
 use super::font_trait::Font;
 use super::img::ImageInputFormat;
+use super::shape::Shape;
 use crate::renderer::{draw_text_mut, FontSetting};
 use crate::Error;
 use image::imageops::overlay;
-use image::{load_from_memory_with_format, DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use image::{
+    load_from_memory_with_format, DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgba,
+    RgbaImage,
+};
 use imageproc::map::map_colors;
 use std::path::Path;
 
@@ -11,18 +15,24 @@ pub use image::ImageOutputFormat;
 
 pub(super) struct Context {
     pub image: Option<RgbaImage>,
+    // Shapes that drawing is clipped by. Pixel is drawn where all of them cover.
+    clips: Vec<Shape>,
 }
 
 impl Context {
     pub fn new(w: u32, h: u32) -> Self {
         let image = RgbaImage::new(w, h);
-        Self { image: Some(image) }
+        Self {
+            image: Some(image),
+            clips: vec![],
+        }
     }
 
     pub fn from_data(data: &[u8], format: ImageInputFormat) -> Result<Self, Error> {
         let image = load_from_memory_with_format(data, format.as_image_format())?;
         Ok(Self {
             image: Some(image.into_rgba8()),
+            clips: vec![],
         })
     }
 
@@ -36,26 +46,90 @@ impl Context {
         Ok(())
     }
 
+    // Draw `buf` at `x` and `y`. Part of the image that is out of the canvas is clipped.
     pub fn draw_image(
         &mut self,
         buf: ImageBuffer<Rgba<u8>, Vec<u8>>,
-        x: u32,
-        y: u32,
+        x: i32,
+        y: i32,
+    ) -> Result<(), Error> {
+        let image = match &mut self.image {
+            Some(image) => image,
+            None => return Err(Error::NotFoundContainerImage),
+        };
+        let (left, top) = ((-x).max(0) as u32, (-y).max(0) as u32);
+        if left >= buf.width() || top >= buf.height() {
+            return Ok(());
+        }
+        let visible = buf.view(left, top, buf.width() - left, buf.height() - top);
+        let (x, y) = (x.max(0) as u32, y.max(0) as u32);
+        if self.clips.is_empty() {
+            overlay(image, &visible, x, y);
+            return Ok(());
+        }
+
+        let clips = &self.clips;
+        for (dx, dy, mut pixel) in visible.pixels() {
+            let (x, y) = (x + dx, y + dy);
+            if x >= image.width() || y >= image.height() {
+                continue;
+            }
+            let coverage = clip_coverage(clips, x, y);
+            if coverage == 0. {
+                continue;
+            }
+            pixel.0[3] = (pixel.0[3] as f32 * coverage).round() as u8;
+            image.get_pixel_mut(x, y).blend(&pixel);
+        }
+
+        Ok(())
+    }
+
+    // Blend `color` over pixels in `area` by coverage that is returned from `coverage` for each pixel.
+    // Area is left, top, right and bottom edges, and it is clipped by the image.
+    pub fn fill(
+        &mut self,
+        area: (i32, i32, i32, i32),
+        color: Rgba<u8>,
+        coverage: impl Fn(u32, u32) -> f32,
     ) -> Result<(), Error> {
         let image = match &mut self.image {
             Some(image) => image,
             None => return Err(Error::NotFoundContainerImage),
         };
-        overlay(image, &buf, x, y);
+        let (left, top, right, bottom) = area;
+        let right = right.min(image.width() as i32);
+        let bottom = bottom.min(image.height() as i32);
+        for y in top.max(0)..bottom {
+            for x in left.max(0)..right {
+                let (x, y) = (x as u32, y as u32);
+                let coverage = coverage(x, y).clamp(0., 1.) * clip_coverage(&self.clips, x, y);
+                if coverage == 0. {
+                    continue;
+                }
+                let mut color = color;
+                color.0[3] = (color.0[3] as f32 * coverage).round() as u8;
+                image.get_pixel_mut(x, y).blend(&color);
+            }
+        }
 
         Ok(())
     }
 
+    // Clip drawing by `shape` until `pop_clip` is called.
+    pub fn push_clip(&mut self, shape: Shape) {
+        self.clips.push(shape);
+    }
+
+    pub fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
     pub fn draw_text(
         &mut self,
         color: Rgba<u8>,
-        x: u32,
-        y: u32,
+        x: i32,
+        y: i32,
         font: &dyn Font,
         setting: &FontSetting,
         text: &str,
@@ -64,7 +138,10 @@ impl Context {
             Some(image) => image,
             None => return Err(Error::NotFoundContainerImage),
         };
-        draw_text_mut(image, color, x, y, font, setting, text);
+        let clips = &self.clips;
+        draw_text_mut(image, color, x, y, font, setting, text, |x, y| {
+            clip_coverage(clips, x, y)
+        });
 
         Ok(())
     }
@@ -104,3 +181,8 @@ impl Context {
         }
     }
 }
+
+// Return how much of the pixel is covered by all of `clips`.
+fn clip_coverage(clips: &[Shape], x: u32, y: u32) -> f32 {
+    clips.iter().map(|clip| clip.coverage(x, y)).product()
+}
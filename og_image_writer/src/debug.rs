@@ -0,0 +1,125 @@
+use super::layout_tree::{LayoutNode, LayoutRect, LayoutTree};
+use super::writer::OGImageWriter;
+use super::Error;
+use image::{Pixel, Rgba};
+
+// Colors are translucent so that painted elements are still visible.
+const MARGIN_BOX_COLOR: Rgba<u8> = Rgba([246, 178, 107, 160]);
+const BORDER_BOX_COLOR: Rgba<u8> = Rgba([111, 168, 220, 200]);
+const FRAGMENT_COLOR: Rgba<u8> = Rgba([147, 196, 125, 200]);
+const BASELINE_COLOR: Rgba<u8> = Rgba([224, 102, 102, 200]);
+const FLEX_AXIS_COLOR: Rgba<u8> = Rgba([194, 123, 160, 160]);
+
+impl OGImageWriter {
+    // Draw computed layout on painted image.
+    pub(super) fn paint_debug(&mut self, layout: &LayoutTree) -> Result<(), Error> {
+        let image = match &mut self.context.image {
+            Some(image) => image,
+            None => return Err(Error::NotFoundContainerImage),
+        };
+        let mut canvas = DebugCanvas(image);
+
+        let (width, height) = (layout.width as i32, layout.height as i32);
+        if self.window.flex_direction.is_column() {
+            canvas.vline(width / 2, 0, height, FLEX_AXIS_COLOR);
+        } else {
+            canvas.hline(0, width, height / 2, FLEX_AXIS_COLOR);
+        }
+
+        canvas.nodes(&layout.children);
+
+        Ok(())
+    }
+}
+
+struct DebugCanvas<'a>(&'a mut image::RgbaImage);
+
+impl DebugCanvas<'_> {
+    fn nodes(&mut self, nodes: &[LayoutNode]) {
+        for node in nodes {
+            self.rect(&node.margin_box, MARGIN_BOX_COLOR);
+            self.rect(&node.rect, BORDER_BOX_COLOR);
+            for fragment in &node.fragments {
+                self.rect(&fragment.rect, FRAGMENT_COLOR);
+                let LayoutRect { x, width, .. } = fragment.rect;
+                self.hline(x, x + width as i32, fragment.baseline, BASELINE_COLOR);
+            }
+            self.nodes(&node.children);
+        }
+    }
+
+    fn blend(&mut self, x: i32, y: i32, color: Rgba<u8>) {
+        if x < 0 || y < 0 || x >= self.0.width() as i32 || y >= self.0.height() as i32 {
+            return;
+        }
+        self.0.get_pixel_mut(x as u32, y as u32).blend(&color);
+    }
+
+    fn hline(&mut self, start: i32, end: i32, y: i32, color: Rgba<u8>) {
+        for x in start..end {
+            self.blend(x, y, color);
+        }
+    }
+
+    fn vline(&mut self, x: i32, start: i32, end: i32, color: Rgba<u8>) {
+        for y in start..end {
+            self.blend(x, y, color);
+        }
+    }
+
+    // Draw outline of `rect`. Corners are drawn once.
+    fn rect(&mut self, rect: &LayoutRect, color: Rgba<u8>) {
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let right = rect.x + rect.width as i32 - 1;
+        let bottom = rect.y + rect.height as i32 - 1;
+        self.hline(rect.x, right + 1, rect.y, color);
+        if bottom > rect.y {
+            self.hline(rect.x, right + 1, bottom, color);
+        }
+        self.vline(rect.x, rect.y + 1, bottom, color);
+        if right > rect.x {
+            self.vline(right, rect.y + 1, bottom, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{Rgba as StyleRgba, Style, WindowStyle};
+
+    #[test]
+    fn test_paint_debug() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(200),
+            height: Some(100),
+            background_color: Some(StyleRgba([255, 255, 255, 255])),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer.set_debug(true);
+        writer
+            .set_text(
+                "Hello",
+                Style {
+                    font_size: 30.,
+                    ..Style::default()
+                },
+                Some(include_bytes!("../../fonts/Roboto-Light.ttf").to_vec()),
+            )
+            .unwrap();
+        let layout = writer.layout();
+        writer.paint().unwrap();
+
+        let image = writer.into_rgba().unwrap();
+        let rect = layout.children[0].fragments[0].rect;
+        let white = image::Rgba([255, 255, 255, 255]);
+        // Fragment outline is drawn on top-left corner.
+        assert_ne!(*image.get_pixel(rect.x as u32, rect.y as u32), white);
+        // Flex axis is drawn at the center of column.
+        assert_ne!(*image.get_pixel(100, 99), white);
+        assert_eq!(*image.get_pixel(199, 99), white);
+    }
+}
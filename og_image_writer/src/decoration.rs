@@ -0,0 +1,201 @@
+use super::context::Context;
+use super::element::Rect;
+use super::shape::Shape;
+use super::style::{BorderStyle, BoxShadow, Style};
+use super::writer::OGImageWriter;
+use super::Error;
+
+impl OGImageWriter {
+    // Paint box shadow, background color and border of element in border box `rect`.
+    pub(super) fn paint_decoration(&mut self, style: &Style, rect: &Rect) -> Result<(), Error> {
+        let has_border = style.border_width > 0 && !matches!(style.border_style, BorderStyle::None);
+        if style.background_color.is_none() && !has_border && style.box_shadow.is_none() {
+            return Ok(());
+        }
+        let context = &mut self.context;
+        let outer = Shape::new(rect, &style.border_radius, 0);
+        let area = (
+            rect.x,
+            rect.y,
+            rect.x + rect.width as i32,
+            rect.y + rect.height as i32,
+        );
+
+        match style.box_shadow {
+            Some(shadow) if !shadow.inset => paint_outer_shadow(context, &shadow, &outer, area)?,
+            _ => {}
+        }
+
+        if let Some(color) = style.background_color {
+            context.fill(area, color.as_image_rgba(), |x, y| outer.coverage(x, y))?;
+        }
+
+        match style.box_shadow {
+            Some(shadow) if shadow.inset => {
+                let padding_box = Shape::new(rect, &style.border_radius, style.border_width);
+                paint_inset_shadow(context, &shadow, &padding_box, area)?;
+            }
+            _ => {}
+        }
+
+        if has_border {
+            let width = style.border_width;
+            let inner = Shape::new(rect, &style.border_radius, width);
+            let border_style = style.border_style;
+            context.fill(area, style.border_color.as_image_rgba(), |x, y| {
+                let coverage = outer.coverage(x, y) - inner.coverage(x, y);
+                if coverage <= 0. || !is_dash(border_style, rect, width, x, y) {
+                    return 0.;
+                }
+                coverage
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+// Paint shadow that is cast by `border_box`. Shadow is not painted under the element.
+fn paint_outer_shadow(
+    context: &mut Context,
+    shadow: &BoxShadow,
+    border_box: &Shape,
+    area: (i32, i32, i32, i32),
+) -> Result<(), Error> {
+    let shape = border_box
+        .translate(shadow.offset_x as f32, shadow.offset_y as f32)
+        .spread(shadow.spread as f32);
+    let sigma = shadow.blur as f32 / 2.;
+    let extent = shadow.spread.max(0) + (sigma * 3.).ceil() as i32;
+    let (left, top, right, bottom) = area;
+    let area = (
+        left.min(left + shadow.offset_x) - extent,
+        top.min(top + shadow.offset_y) - extent,
+        right.max(right + shadow.offset_x) + extent,
+        bottom.max(bottom + shadow.offset_y) + extent,
+    );
+    context.fill(area, shadow.color.as_image_rgba(), |x, y| {
+        shape.blurred_coverage(x, y, sigma) * (1. - border_box.coverage(x, y))
+    })
+}
+
+// Paint shadow that is cast inward by edge of `padding_box`.
+fn paint_inset_shadow(
+    context: &mut Context,
+    shadow: &BoxShadow,
+    padding_box: &Shape,
+    area: (i32, i32, i32, i32),
+) -> Result<(), Error> {
+    let shape = padding_box
+        .translate(shadow.offset_x as f32, shadow.offset_y as f32)
+        .spread(-shadow.spread as f32);
+    let sigma = shadow.blur as f32 / 2.;
+    context.fill(area, shadow.color.as_image_rgba(), |x, y| {
+        (1. - shape.blurred_coverage(x, y, sigma)) * padding_box.coverage(x, y)
+    })
+}
+
+// Return whether pixel at `x` and `y` is painted by `border_style`.
+// Dashes are arranged along each side, and corners are always painted.
+fn is_dash(border_style: BorderStyle, rect: &Rect, width: u32, x: u32, y: u32) -> bool {
+    let segment = match border_style {
+        BorderStyle::None => return false,
+        BorderStyle::Solid => return true,
+        BorderStyle::Dashed => width * 3,
+        BorderStyle::Dotted => width,
+    };
+    let (x, y) = ((x as i32 - rect.x) as u32, (y as i32 - rect.y) as u32);
+    let is_horizontal = y < width || y + width >= rect.height;
+    let is_vertical = x < width || x + width >= rect.width;
+    match (is_horizontal, is_vertical) {
+        (true, true) => true,
+        (true, false) => ((x - width) / segment).is_multiple_of(2),
+        (false, true) => ((y - width) / segment).is_multiple_of(2),
+        (false, false) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{BorderRadius, Rgba as StyleRgba, WindowStyle};
+
+    #[test]
+    fn test_paint_decoration() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(100),
+            height: Some(100),
+            background_color: Some(StyleRgba([255, 255, 255, 255])),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer.process_background().unwrap();
+        let style = Style {
+            background_color: Some(StyleRgba([0, 0, 255, 255])),
+            border_width: 4,
+            border_color: StyleRgba([255, 0, 0, 255]),
+            border_radius: BorderRadius(20, 20, 20, 20),
+            ..Style::default()
+        };
+        writer
+            .paint_decoration(&style, &Rect::new(10, 10, 80, 80))
+            .unwrap();
+
+        let image = writer.into_rgba().unwrap();
+        let white = image::Rgba([255, 255, 255, 255]);
+        // Corner outside of radius is not painted.
+        assert_eq!(*image.get_pixel(11, 11), white);
+        assert_eq!(*image.get_pixel(50, 11), image::Rgba([255, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(50, 50), image::Rgba([0, 0, 255, 255]));
+        // Edge of the arc is antialiased.
+        let edge = image.get_pixel(11, 22);
+        assert!(edge.0[1] > 0 && edge.0[1] < 255);
+    }
+
+    #[test]
+    fn test_paint_box_shadow() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(100),
+            height: Some(100),
+            background_color: Some(StyleRgba([255, 255, 255, 255])),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer.process_background().unwrap();
+        let shadow = BoxShadow {
+            offset_x: 10,
+            offset_y: 10,
+            blur: 10,
+            ..BoxShadow::default()
+        };
+        let style = Style {
+            box_shadow: Some(shadow),
+            ..Style::default()
+        };
+        writer
+            .paint_decoration(&style, &Rect::new(10, 10, 40, 40))
+            .unwrap();
+        let style = Style {
+            box_shadow: Some(BoxShadow {
+                inset: true,
+                ..shadow
+            }),
+            ..Style::default()
+        };
+        writer
+            .paint_decoration(&style, &Rect::new(60, 10, 30, 80))
+            .unwrap();
+
+        let image = writer.into_rgba().unwrap();
+        let white = image::Rgba([255, 255, 255, 255]);
+        // Shadow is not painted under the element.
+        assert_eq!(*image.get_pixel(30, 30), white);
+        assert!(image.get_pixel(55, 55).0[0] < 128);
+        // Shadow fades out by blur.
+        assert!(image.get_pixel(45, 61).0[0] > image.get_pixel(45, 55).0[0]);
+        assert_eq!(*image.get_pixel(80, 95), white);
+        // Inset shadow is cast from top left edges.
+        assert!(image.get_pixel(61, 50).0[0] < 128);
+        assert!(image.get_pixel(88, 50).0[0] > 240);
+    }
+}
@@ -1,13 +1,16 @@
 use super::font::FontArc;
+use super::font_context::FontContext;
 use super::layout::TextArea;
-use super::style::{Margin, Position, Style};
+use super::style::{Margin, Padding, Position, Style, TextAlign, WindowStyle};
+use super::writer::{Content, Tree};
 use image::{ImageBuffer, Rgba};
 use std::ops::Range;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) enum Element {
     Img(Option<Img>),
     Text(Option<Text>),
+    Container(Option<Container>),
 }
 
 impl Element {
@@ -15,29 +18,89 @@ impl Element {
         match self {
             Element::Img(Some(img)) => matches!(img.style.position, Position::Absolute),
             Element::Text(Some(text)) => matches!(text.style.position, Position::Absolute),
+            Element::Container(Some(container)) => {
+                matches!(container.style.position, Position::Absolute)
+            }
             _ => false,
         }
     }
 
+    pub(super) fn style(&self) -> Option<&Style> {
+        match self {
+            Element::Img(Some(img)) => Some(&img.style),
+            Element::Text(Some(text)) => Some(&text.style),
+            Element::Container(Some(container)) => Some(&container.style),
+            _ => None,
+        }
+    }
+
     pub(super) fn margin(&self) -> Margin {
         match self {
             Element::Img(Some(img)) => img.style.margin,
             Element::Text(Some(text)) => text.style.margin,
+            Element::Container(Some(container)) => container.style.margin,
             _ => Margin::default(),
         }
     }
+
+    // Return size of margin box.
+    pub(super) fn margin_box_size(&self) -> (i32, i32) {
+        let Margin(margin_top, margin_right, margin_bottom, margin_left) = self.margin();
+        let (width, height) = match self {
+            Element::Img(Some(img)) => (img.rect.width as i32, img.rect.height as i32),
+            Element::Text(Some(text)) => {
+                let (inset_width, inset_height) = text.style.inset_size();
+                (
+                    text.metrics.max_line_width as i32 + inset_width as i32,
+                    text.metrics.total_height as i32 + inset_height as i32,
+                )
+            }
+            Element::Container(Some(container)) => {
+                (container.rect.width as i32, container.rect.height as i32)
+            }
+            _ => (0, 0),
+        };
+        (
+            width + margin_left + margin_right,
+            height + margin_top + margin_bottom,
+        )
+    }
+
+    // Move element and its children.
+    pub(super) fn translate(&mut self, dx: i32, dy: i32) {
+        fn translate_rect(rect: &mut Rect, dx: i32, dy: i32) {
+            rect.x += dx;
+            rect.y += dy;
+        }
+
+        match self {
+            Element::Img(Some(img)) => translate_rect(&mut img.rect, dx, dy),
+            Element::Text(Some(text)) => {
+                for fragment in &mut text.fragments {
+                    translate_rect(&mut fragment.rect, dx, dy);
+                }
+            }
+            Element::Container(Some(container)) => {
+                translate_rect(&mut container.rect, dx, dy);
+                for elm in &mut container.tree.0 {
+                    elm.translate(dx, dy);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(super) struct Rect {
-    pub(super) x: u32,
-    pub(super) y: u32,
+    pub(super) x: i32,
+    pub(super) y: i32,
     pub(super) width: u32,
     pub(super) height: u32,
 }
 
 impl Rect {
-    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
         Rect {
             x,
             y,
@@ -45,9 +108,31 @@ impl Rect {
             height,
         }
     }
+
+    // Return rectangle that is moved inward by `inset` on each side.
+    pub(super) fn shrink(&self, inset: &Padding) -> Rect {
+        let Padding(top, right, bottom, left) = *inset;
+        Rect::new(
+            self.x + left as i32,
+            self.y + top as i32,
+            self.width.saturating_sub(left + right),
+            self.height.saturating_sub(top + bottom),
+        )
+    }
+
+    // Return rectangle that is moved outward by `inset` on each side.
+    pub(super) fn expand(&self, inset: &Padding) -> Rect {
+        let Padding(top, right, bottom, left) = *inset;
+        Rect::new(
+            self.x - left as i32,
+            self.y - top as i32,
+            self.width + left + right,
+            self.height + top + bottom,
+        )
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) struct Img {
     pub(super) buf: ImageBuffer<Rgba<u8>, Vec<u8>>,
     pub(super) rect: Rect,
@@ -55,12 +140,25 @@ pub(super) struct Img {
 }
 
 impl Img {
-    pub fn new(buf: ImageBuffer<Rgba<u8>, Vec<u8>>, rect: Rect, style: Style) -> Self {
+    // Rect is border box that is resolved from size of the image and style.
+    pub fn new(buf: ImageBuffer<Rgba<u8>, Vec<u8>>, width: u32, height: u32, style: Style) -> Self {
+        let (inset_width, inset_height) = style.inset_size();
+        let rect = Rect::new(
+            0,
+            0,
+            style.content_width(width) + inset_width,
+            style.content_height(height) + inset_height,
+        );
         Img { buf, rect, style }
     }
+
+    // Return content box, that image is drawn in.
+    pub(super) fn content_rect(&self) -> Rect {
+        self.rect.shrink(&self.style.inset())
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) struct Fragment {
     pub(super) range: Range<usize>,
     pub(super) rect: Rect,
@@ -72,7 +170,7 @@ impl Fragment {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(super) struct LineMetrics {
     pub total_height: u32,
     pub max_line_height: f32,
@@ -89,7 +187,7 @@ impl LineMetrics {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) struct Text {
     pub(super) text: String,
     pub(super) metrics: LineMetrics,
@@ -98,9 +196,14 @@ pub(super) struct Text {
     // TODO: optimize static lifetime
     pub(super) font: Option<FontArc>,
     pub(super) textarea: TextArea,
+    // TextArea before line breaking. This is used to break lines again in flexed width.
+    pub(super) source: TextArea,
+    // FontContext that global fonts in `textarea` were selected from.
+    pub(super) font_context: FontContext,
 }
 
 impl Text {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         text: String,
         fragments: Vec<Fragment>,
@@ -108,6 +211,8 @@ impl Text {
         style: Style,
         font: Option<FontArc>,
         textarea: TextArea,
+        source: TextArea,
+        font_context: FontContext,
     ) -> Self {
         Text {
             text,
@@ -116,6 +221,60 @@ impl Text {
             style,
             font,
             textarea,
+            source,
+            font_context,
+        }
+    }
+
+    // Return content box, that lines are laid out in.
+    // Position is resolved from the first line because lines are moved one by one.
+    pub(super) fn content_rect(&self) -> Rect {
+        let fragment = match self.fragments.first() {
+            Some(fragment) => fragment,
+            None => return Rect::default(),
+        };
+        Rect::new(
+            fragment.rect.x - self.line_offset(fragment),
+            fragment.rect.y,
+            self.metrics.max_line_width as u32,
+            self.metrics.total_height,
+        )
+    }
+
+    // Return content box that is extended to the glyphs drawn below the last line.
+    // Glyphs are drawn in the font size from the top of each line, that is taller than the line height.
+    pub(super) fn drawn_rect(&self) -> Rect {
+        let rect = self.content_rect();
+        let font_size = self.textarea.max_font_size(&self.style).ceil() as i32;
+        let bottom = self
+            .fragments
+            .iter()
+            .map(|fragment| fragment.rect.y + font_size)
+            .max()
+            .unwrap_or(rect.y);
+        let height = (bottom - rect.y).max(rect.height as i32) as u32;
+        Rect::new(rect.x, rect.y, rect.width, height)
+    }
+
+    // Return offset of `fragment` from the start of the content box by `text_align`.
+    pub(super) fn line_offset(&self, fragment: &Fragment) -> i32 {
+        let max_line_width = self.metrics.max_line_width as i32;
+        match self.style.text_align {
+            TextAlign::Start => 0,
+            TextAlign::Center => max_line_width / 2 - fragment.rect.width as i32 / 2,
+            TextAlign::End => max_line_width - fragment.rect.width as i32,
         }
     }
 }
+
+#[derive(Debug, Clone)]
+pub(super) struct Container {
+    pub(super) rect: Rect,
+    pub(super) style: Style,
+    // Window of the container. Width and height are resolved by content if they are not specified.
+    pub(super) window: WindowStyle,
+    pub(super) content: Content,
+    pub(super) tree: Tree,
+    // Background that is painted before children.
+    pub(super) background: Option<ImageBuffer<Rgba<u8>, Vec<u8>>>,
+}
@@ -1,4 +1,5 @@
 use image::ImageError;
+use std::io;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -15,4 +16,6 @@ pub enum Error {
     OutOfRangeText,
     #[error("Could not found specified font family")]
     NotFoundSpecifiedFontFamily,
+    #[error("io error: {0}")]
+    IoError(#[from] io::Error),
 }
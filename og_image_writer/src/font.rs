@@ -1,16 +1,28 @@
 use super::font_trait::Font;
+use super::woff;
 use super::Error;
 use ab_glyph::{
-    Font as AbFont, FontArc as AbFontArc, Glyph, GlyphId, OutlinedGlyph, ScaleFont as AbScaleFont,
+    Font as AbFont, FontArc as AbFontArc, FontVec, Glyph, GlyphId, OutlinedGlyph,
+    ScaleFont as AbScaleFont,
 };
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub(super) struct FontMetrics {
     pub height: f32,
     pub width: f32,
 }
 
-#[derive(Debug)]
-pub(super) struct FontArc(AbFontArc);
+// Each loaded font gets unique id so that glyph cache entries are never shared between fonts.
+static NEXT_FONT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone)]
+pub(super) struct FontArc(AbFontArc, usize);
+
+impl FontArc {
+    fn new(font: AbFontArc) -> FontArc {
+        FontArc(font, NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
 
 impl Font for FontArc {
     fn glyph_id(&self, ch: char) -> GlyphId {
@@ -36,13 +48,23 @@ impl Font for FontArc {
     fn outline_glyph(&self, glyph: Glyph, scale: f32) -> Option<OutlinedGlyph> {
         self.0.as_scaled(scale).outline_glyph(glyph)
     }
+
+    fn cache_id(&self) -> Option<usize> {
+        Some(self.1)
+    }
 }
 
 pub(super) const WHITESPACE_EM: f32 = 0.2;
 
 pub(super) fn create_font(data: Vec<u8>) -> Result<FontArc, Error> {
-    match AbFontArc::try_from_vec(data) {
-        Ok(font) => Ok(FontArc(font)),
+    create_font_with_index(data, 0)
+}
+
+// WOFF and WOFF2 are decoded to sfnt before parsing.
+pub(super) fn create_font_with_index(data: Vec<u8>, index: u32) -> Result<FontArc, Error> {
+    let data = woff::decode(data)?;
+    match FontVec::try_from_vec_and_index(data, index) {
+        Ok(font) => Ok(FontArc::new(AbFontArc::new(font))),
         Err(_) => Err(Error::InvalidFontBytes),
     }
 }
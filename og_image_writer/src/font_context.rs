@@ -1,126 +1,529 @@
 use crate::{
-    font::{create_font, match_font_family},
+    font::{create_font_with_index, match_font_family, FontArc},
+    font_info::{face_count, weight_distance},
     font_trait::Font,
-    Error,
+    style::{FontStyle, Style},
+    woff, Error,
 };
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+pub use crate::font_info::FontFaceInfo;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(super) struct FontIndex(pub(super) usize);
 
 #[derive(Debug, Clone, PartialEq)]
 pub(super) enum FontIndexStore {
+    // Font held by FontContext, that is global fallback font or registered font family.
     Global(FontIndex),
     Parent(FontIndex),
     Child(FontIndex),
 }
 
-pub(super) struct FontStore(Vec<Box<dyn Font>>);
+// Font that is held by FontContext.
+// Font loaded from file system is parsed lazily when the font is used at first.
+enum FontSlot {
+    Loaded(Box<dyn Font>),
+    File {
+        path: PathBuf,
+        index: u32,
+        font: OnceLock<Option<FontArc>>,
+    },
+}
 
-impl FontStore {
-    pub(super) fn borrow_font(&self, idx: &FontIndex) -> &dyn Font {
-        &*self.0[idx.0]
+impl FontSlot {
+    fn get(&self) -> Option<&dyn Font> {
+        match self {
+            FontSlot::Loaded(font) => Some(&**font),
+            FontSlot::File { path, index, font } => font
+                .get_or_init(|| {
+                    let data = fs::read(path).ok()?;
+                    create_font_with_index(data, *index).ok()
+                })
+                .as_ref()
+                .map(|font| font as &dyn Font),
+        }
     }
 }
 
-mod font_context_store {
-    use super::{Font, FontIndex, FontStore};
-    use std::cell::RefCell;
-    use std::rc::Rc;
+// Font registered with family name.
+struct FamilyFace {
+    family: String,
+    weight: u16,
+    italic: bool,
+    idx: FontIndex,
+}
+
+#[derive(Default)]
+pub(super) struct FontStore {
+    // All fonts held by FontContext. `FontIndex` points to this list.
+    // Fonts are shared so that they can be used without holding the lock.
+    // Removed fonts are `None`, and their indexes are not reused by fonts pushed later.
+    fonts: Vec<Option<Arc<FontSlot>>>,
+    // Global fallback fonts pushed with `FontContext::push`.
+    fallbacks: Vec<FontIndex>,
+    // Fonts registered with `FontContext::register` or loaded from file system.
+    families: Vec<FamilyFace>,
+}
 
-    thread_local! {
-        static FONT_CONTEXT_STORE: Rc<RefCell<FontStore>> = Rc::new(RefCell::new(FontStore(vec![])));
+impl FontStore {
+    fn has_glyph(&self, idx: &FontIndex, ch: char) -> bool {
+        match self.font(idx).and_then(|font| font.get()) {
+            Some(font) => match_font_family(ch, font),
+            None => false,
+        }
     }
 
-    pub(super) fn get_mut() -> Rc<RefCell<FontStore>> {
-        FONT_CONTEXT_STORE.with(|f| f.clone())
+    fn font(&self, idx: &FontIndex) -> Option<&Arc<FontSlot>> {
+        self.fonts.get(idx.0)?.as_ref()
     }
 
-    pub fn clear() {
-        FONT_CONTEXT_STORE.with(|f| {
-            let mut store = f.borrow_mut();
-            store.0.clear();
-        });
+    fn push_font(&mut self, font: FontSlot) -> FontIndex {
+        self.fonts.push(Some(Arc::new(font)));
+        FontIndex(self.fonts.len() - 1)
     }
 
-    pub fn len() -> usize {
-        FONT_CONTEXT_STORE.with(|f| {
-            let store = f.borrow();
-            store.0.len()
-        })
+    fn push_family(&mut self, family: &str, weight: u16, italic: bool, font: FontSlot) {
+        let idx = self.push_font(font);
+        let face = self.families.iter_mut().find(|face| {
+            face.family.eq_ignore_ascii_case(family)
+                && face.weight == weight
+                && face.italic == italic
+        });
+        match face {
+            Some(face) => face.idx = idx,
+            None => self.families.push(FamilyFace {
+                family: family.to_string(),
+                weight,
+                italic,
+                idx,
+            }),
+        }
     }
 
-    pub(super) fn with<F, T>(idx: &FontIndex, f: F) -> T
-    where
-        F: FnOnce(&dyn Font) -> T,
-    {
-        let ctx = get_mut();
-        let store = ctx.borrow();
-        let font = store.borrow_font(idx);
-        f(font)
+    // Find the closest face in the family for the specified weight and style.
+    fn find_family(&self, family: &str, weight: u16, italic: bool) -> Option<&FontIndex> {
+        self.families
+            .iter()
+            .filter(|face| face.family.eq_ignore_ascii_case(family))
+            .min_by_key(|face| (face.italic != italic, weight_distance(weight, face.weight)))
+            .map(|face| &face.idx)
     }
 }
 
-// This strut do not have nothing.
-// But this struct provide operation for font_context_store local thread.
-// If you want to use font_context_store, you must call method from FontContext.
-// That is FontContext has role for access control for font_context_store.
+/// Fonts used to render text, that are global fallback fonts and registered font families.
+///
+/// FontContext is cheap to clone and every clone shares the same fonts,
+/// so that one FontContext can be shared with many writers across threads.
+/// Pass it to the writer with [OGImageWriter::set_font_context](crate::writer::OGImageWriter::set_font_context).
+#[derive(Clone, Default)]
+pub struct FontContext {
+    store: Arc<RwLock<FontStore>>,
+}
 
-/// You can specify global fallback font by using `FontContext::push`.
-/// NOTE: FontContext will be shared with other instance.
-#[derive(Default)]
-pub struct FontContext;
+impl fmt::Debug for FontContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let store = self.read();
+        f.debug_struct("FontContext")
+            .field("fonts", &store.fonts.iter().flatten().count())
+            .field("fallbacks", &store.fallbacks.len())
+            .field("families", &store.families.len())
+            .finish()
+    }
+}
 
 impl FontContext {
     pub fn new() -> FontContext {
-        FontContext
+        FontContext::default()
+    }
+
+    // Fonts are immutable once pushed, so the store is still consistent after panic.
+    fn read(&self) -> RwLockReadGuard<'_, FontStore> {
+        self.store.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, FontStore> {
+        self.store.write().unwrap_or_else(|e| e.into_inner())
     }
 
     // TODO: optimize data structure for memory performance
-    pub fn push(&mut self, data: Vec<u8>) -> Result<(), Error> {
-        let store = font_context_store::get_mut();
-        let mut store = store.borrow_mut();
-        let font = create_font(data)?;
-        store.0.push(Box::new(font));
+    pub fn push(&self, data: Vec<u8>) -> Result<(), Error> {
+        self.push_with_index(data, 0)
+    }
+
+    /// Push the face at `index` in TrueType/OpenType collection as global fallback font.
+    /// You can list faces in the collection with [collection_faces].
+    pub fn push_with_index(&self, data: Vec<u8>, index: u32) -> Result<(), Error> {
+        let font = create_font_with_index(data, index)?;
+        let mut store = self.write();
+        let idx = store.push_font(FontSlot::Loaded(Box::new(font)));
+        store.fallbacks.push(idx);
+        Ok(())
+    }
+
+    /// Register font with family name.
+    /// Registered font is used when the family name is specified in `Style::font_family`.
+    /// Weight and style are read from the font, and the font that has same family, weight and style is replaced.
+    pub fn register(&self, family: &str, data: Vec<u8>) -> Result<(), Error> {
+        self.register_with_index(family, data, 0)
+    }
+
+    /// Register the face at `index` in TrueType/OpenType collection with family name.
+    pub fn register_with_index(
+        &self,
+        family: &str,
+        data: Vec<u8>,
+        index: u32,
+    ) -> Result<(), Error> {
+        let data = woff::decode(data)?;
+        let (weight, italic) = match FontFaceInfo::parse(&data, index) {
+            Some(info) => (info.weight, info.italic),
+            None => (400, false),
+        };
+        let font = create_font_with_index(data, index)?;
+        let mut store = self.write();
+        store.push_family(family, weight, italic, FontSlot::Loaded(Box::new(font)));
         Ok(())
     }
 
     #[cfg(test)]
-    pub(crate) fn push_font(&mut self, font: Box<dyn Font>) {
-        let store = font_context_store::get_mut();
-        let mut store = store.borrow_mut();
-        store.0.push(font);
+    pub(crate) fn register_font(&self, family: &str, font: Box<dyn Font>) {
+        let mut store = self.write();
+        store.push_family(family, 400, false, FontSlot::Loaded(font));
+    }
+
+    /// Register all faces in the font file with family name read from the font.
+    /// Font file is parsed lazily when the font is used at first.
+    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<FontFaceInfo>, Error> {
+        let path = path.as_ref();
+        let data = fs::read(path)?;
+        let faces = collection_faces(&data)?;
+
+        let mut store = self.write();
+        for face in &faces {
+            store.push_family(
+                &face.family,
+                face.weight,
+                face.italic,
+                FontSlot::File {
+                    path: path.to_path_buf(),
+                    index: face.index,
+                    font: OnceLock::new(),
+                },
+            );
+        }
+
+        Ok(faces)
+    }
+
+    /// Scan directory recursively and register TrueType/OpenType fonts, collections and WOFF/WOFF2 fonts.
+    /// Files that could not be parsed as font and subdirectories that could not be read are skipped.
+    /// Symlinked directories are not followed.
+    pub fn load_dir<P: AsRef<Path>>(&self, path: P) -> Result<Vec<FontFaceInfo>, Error> {
+        let mut faces = vec![];
+        for entry in fs::read_dir(path)?.flatten() {
+            let path = entry.path();
+            // File type of entry is not resolved through symlink, so symlink to a parent is not looped.
+            let is_dir = match entry.file_type() {
+                Ok(file_type) => file_type.is_dir(),
+                Err(_) => continue,
+            };
+            if is_dir {
+                if let Ok(mut loaded) = self.load_dir(&path) {
+                    faces.append(&mut loaded);
+                }
+            } else if is_font_file(&path) {
+                if let Ok(mut loaded) = self.load_file(&path) {
+                    faces.append(&mut loaded);
+                }
+            }
+        }
+        Ok(faces)
+    }
+
+    /// Return true if font is registered with the family name.
+    pub fn has_family(&self, family: &str) -> bool {
+        let store = self.read();
+        store.find_family(family, 400, false).is_some()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn push_font(&self, font: Box<dyn Font>) {
+        let mut store = self.write();
+        let idx = store.push_font(FontSlot::Loaded(font));
+        store.fallbacks.push(idx);
     }
 
+    /// Remove all fonts. This affects every clone of this FontContext.
+    /// Texts that are already set with the removed fonts fail to be painted.
     pub fn clear(&self) {
-        // Clear global memory cache
-        font_context_store::clear();
+        let mut store = self.write();
+        for font in &mut store.fonts {
+            *font = None;
+        }
+        store.fallbacks.clear();
+        store.families.clear();
     }
 
+    /// Return the number of global fallback fonts.
+    /// Fonts that are registered with family name are not counted.
     pub fn len(&self) -> usize {
-        font_context_store::len()
+        self.read().fallbacks.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        font_context_store::len() == 0
+        self.len() == 0
+    }
+
+    /// Return true if `other` shares the same fonts with this FontContext.
+    pub fn ptr_eq(&self, other: &FontContext) -> bool {
+        Arc::ptr_eq(&self.store, &other.store)
     }
 
     pub(super) fn select_font_family(&self, ch: char) -> Result<FontIndex, Error> {
-        let store = font_context_store::get_mut();
-        let font_list = &store.borrow().0;
-        for (i, font) in font_list.iter().enumerate() {
-            let has_font = match_font_family(ch, &**font);
+        let store = self.read();
+        for idx in &store.fallbacks {
+            let has_font = store.has_glyph(idx, ch);
             if has_font {
-                return Ok(FontIndex(i));
+                return Ok(idx.clone());
             }
         }
 
         Err(Error::NotFoundSpecifiedFontFamily)
     }
 
-    pub(super) fn with<F, T>(&self, idx: &FontIndex, f: F) -> T
+    // Select the first registered font that has `ch` from `style.font_family`.
+    // Unregistered family names are skipped.
+    pub(super) fn select_registered_family(&self, style: &Style, ch: char) -> Option<FontIndex> {
+        let store = self.read();
+        let italic = matches!(style.font_style, FontStyle::Italic);
+        style
+            .font_family
+            .iter()
+            .filter_map(|family| store.find_family(family, style.font_weight, italic))
+            .find(|idx| store.has_glyph(idx, ch))
+            .cloned()
+    }
+
+    // Select font from `style.font_family` first, and then from global fallback fonts.
+    pub(super) fn select_font_family_with(
+        &self,
+        style: &Style,
+        ch: char,
+    ) -> Result<FontIndex, Error> {
+        match self.select_registered_family(style, ch) {
+            Some(idx) => Ok(idx),
+            None => self.select_font_family(ch),
+        }
+    }
+
+    // Call `f` with the font that is selected by FontContext.
+    // Error is returned if the font is removed by `clear` or can not be loaded.
+    // The lock is released before `f` is called, so `f` can use FontContext again.
+    pub(super) fn with<F, T>(&self, idx: &FontIndex, f: F) -> Result<T, Error>
     where
         F: FnOnce(&dyn Font) -> T,
     {
-        font_context_store::with(idx, f)
+        let font = self
+            .read()
+            .font(idx)
+            .cloned()
+            .ok_or(Error::NotFoundSpecifiedFontFamily)?;
+        let font = font.get().ok_or(Error::NotFoundSpecifiedFontFamily)?;
+        Ok(f(font))
+    }
+}
+
+/// Return faces in the font data.
+/// TrueType/OpenType collection like `.ttc` has multiple faces, and the others have one face.
+pub fn collection_faces(data: &[u8]) -> Result<Vec<FontFaceInfo>, Error> {
+    let decoded;
+    let data = if woff::is_woff(data) {
+        decoded = woff::decode(data.to_vec())?;
+        &decoded[..]
+    } else {
+        data
+    };
+
+    let faces: Vec<FontFaceInfo> = (0..face_count(data))
+        .filter_map(|index| FontFaceInfo::parse(data, index))
+        .collect();
+    if faces.is_empty() {
+        return Err(Error::InvalidFontBytes);
+    }
+    Ok(faces)
+}
+
+fn is_font_file(path: &Path) -> bool {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => ["ttf", "otf", "ttc", "otc", "woff", "woff2"]
+            .iter()
+            .any(|font_ext| ext.eq_ignore_ascii_case(font_ext)),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::WindowStyle;
+    use crate::writer::OGImageWriter;
+
+    const ROBOTO: &[u8] = include_bytes!("../../fonts/Roboto-Light.ttf");
+    const OPEN_SANS: &[u8] = include_bytes!("../../fonts/OpenSansCondensed-Light.ttf");
+
+    // Bundle fonts into TrueType collection.
+    fn create_collection(fonts: &[&[u8]]) -> Vec<u8> {
+        let mut data = b"ttcf".to_vec();
+        data.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+        data.extend_from_slice(&(fonts.len() as u32).to_be_bytes());
+        let mut offset = 12 + 4 * fonts.len();
+        for font in fonts {
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            offset += (font.len() + 3) & !3;
+        }
+        for font in fonts {
+            let base = data.len() as u32;
+            let mut font = font.to_vec();
+            let num_tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+            for i in 0..num_tables {
+                let pos = 12 + i * 16 + 8;
+                let table_offset = u32::from_be_bytes(font[pos..pos + 4].try_into().unwrap());
+                font[pos..pos + 4].copy_from_slice(&(table_offset + base).to_be_bytes());
+            }
+            data.append(&mut font);
+            data.resize((data.len() + 3) & !3, 0);
+        }
+        data
+    }
+
+    #[test]
+    fn test_collection_faces() {
+        let collection = create_collection(&[ROBOTO, OPEN_SANS]);
+
+        let faces = collection_faces(&collection).unwrap();
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[0].family, "Roboto");
+        assert_eq!(faces[0].index, 0);
+        assert_eq!(faces[1].family, "Open Sans Condensed");
+        assert_eq!(faces[1].index, 1);
+
+        assert_eq!(collection_faces(ROBOTO).unwrap().len(), 1);
+        assert!(collection_faces(&[0; 16]).is_err());
+    }
+
+    #[test]
+    fn test_register_with_index() {
+        let collection = create_collection(&[ROBOTO, OPEN_SANS]);
+
+        let font_context = FontContext::new();
+        font_context
+            .register_with_index("Condensed", collection.clone(), 1)
+            .unwrap();
+        font_context.push_with_index(collection, 0).unwrap();
+        assert!(font_context
+            .register_with_index("Unknown", ROBOTO.to_vec(), 1)
+            .is_err());
+
+        assert!(font_context.has_family("Condensed"));
+        assert_eq!(font_context.len(), 1);
+    }
+
+    #[test]
+    fn test_clear() {
+        let font_context = FontContext::new();
+        font_context.push(ROBOTO.to_vec()).unwrap();
+        let idx = font_context.select_font_family('a').unwrap();
+
+        // Index of removed font does not point to the font pushed later.
+        font_context.clear();
+        assert!(font_context.with(&idx, |_| ()).is_err());
+        font_context.push(OPEN_SANS.to_vec()).unwrap();
+        assert!(font_context.with(&idx, |_| ()).is_err());
+
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(100),
+            height: Some(100),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer.set_font_context(&font_context);
+        writer.set_text("Hello", Style::default(), None).unwrap();
+        font_context.clear();
+        assert!(writer.paint().is_err());
+    }
+
+    #[test]
+    fn test_share_between_threads() {
+        let font_context = FontContext::new();
+        font_context.push(ROBOTO.to_vec()).unwrap();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let font_context = font_context.clone();
+                std::thread::spawn(move || {
+                    let idx = font_context.select_font_family('a').unwrap();
+                    font_context
+                        .with(&idx, |font| font.glyph_id('a').0)
+                        .unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_ne!(handle.join().unwrap(), 0);
+        }
+
+        // Fonts pushed to clone are shared, but other FontContext is independent.
+        let cloned = font_context.clone();
+        cloned.push(OPEN_SANS.to_vec()).unwrap();
+        assert_eq!(font_context.len(), 2);
+        assert!(font_context.ptr_eq(&cloned));
+        assert!(FontContext::new().is_empty());
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let font_context = FontContext::new();
+        let faces = font_context
+            .load_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../fonts"))
+            .unwrap();
+
+        assert_eq!(faces.len(), 3);
+        assert!(font_context.has_family("roboto"));
+        assert!(!font_context.has_family("Unknown"));
+
+        let style = Style {
+            font_family: vec!["Unknown".to_string(), "Roboto".to_string()],
+            font_weight: 700,
+            ..Style::default()
+        };
+        assert!(font_context.select_registered_family(&style, 'a').is_some());
+        assert!(font_context
+            .select_registered_family(&style, 'あ')
+            .is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_dir_with_symlink_loop() {
+        let dir =
+            std::env::temp_dir().join(format!("og_image_writer_fonts_{}", std::process::id()));
+        let nested = dir.join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::copy(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../fonts/Roboto-Light.ttf"),
+            nested.join("Roboto-Light.ttf"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(&dir, nested.join("parent")).unwrap();
+
+        let faces = FontContext::new().load_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(faces.unwrap().len(), 1);
     }
 }
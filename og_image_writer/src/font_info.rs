@@ -0,0 +1,116 @@
+use ttf_parser::{fonts_in_collection, name_id, Face, PlatformId};
+
+// English (United States) language id on Windows platform.
+const WINDOWS_ENGLISH_LANGUAGE_ID: u16 = 0x0409;
+
+/// Metadata read from the `name` and `OS/2` tables of a font face.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontFaceInfo {
+    /// Family name like `Noto Sans CJK JP`.
+    pub family: String,
+    /// Subfamily name like `Bold Italic`.
+    pub subfamily: String,
+    /// Weight class between 1 and 1000. `400` is normal and `700` is bold.
+    pub weight: u16,
+    pub italic: bool,
+    /// Face index in TrueType/OpenType collection. This is always `0` for single font file.
+    pub index: u32,
+}
+
+impl FontFaceInfo {
+    pub(super) fn parse(data: &[u8], index: u32) -> Option<FontFaceInfo> {
+        let face = Face::parse(data, index).ok()?;
+
+        // Typographic family is preferred because legacy family is limited to four styles.
+        let family = face_name(&face, name_id::TYPOGRAPHIC_FAMILY)
+            .or_else(|| face_name(&face, name_id::FAMILY))?;
+        let subfamily = face_name(&face, name_id::TYPOGRAPHIC_SUBFAMILY)
+            .or_else(|| face_name(&face, name_id::SUBFAMILY))
+            .unwrap_or_default();
+
+        Some(FontFaceInfo {
+            family,
+            subfamily,
+            weight: face.weight().to_number(),
+            italic: face.is_italic() || face.is_oblique(),
+            index,
+        })
+    }
+}
+
+// Return number of faces in font data.
+// Single font file is treated as collection that has one face.
+pub(super) fn face_count(data: &[u8]) -> u32 {
+    fonts_in_collection(data).unwrap_or(1)
+}
+
+fn face_name(face: &Face, id: u16) -> Option<String> {
+    let mut fallback = None;
+    for name in face.names() {
+        if name.name_id != id {
+            continue;
+        }
+        let is_english = matches!(name.platform_id, PlatformId::Windows)
+            && name.language_id == WINDOWS_ENGLISH_LANGUAGE_ID;
+        match name.to_string() {
+            Some(s) if is_english => return Some(s),
+            Some(s) if fallback.is_none() => fallback = Some(s),
+            _ => {}
+        }
+    }
+    fallback
+}
+
+// Return sort key for CSS font matching algorithm.
+// Smaller key is closer to `desired` weight.
+// See https://www.w3.org/TR/css-fonts-4/#font-style-matching
+pub(super) fn weight_distance(desired: u16, actual: u16) -> (u8, u16) {
+    if actual == desired {
+        return (0, 0);
+    }
+    // Weights between desired weight and 500 are checked first in ascending order.
+    if (400..=500).contains(&desired) && (desired..=500).contains(&actual) {
+        return (0, actual - desired);
+    }
+    // Heavier weights are checked before lighter weights when desired weight is bold.
+    let is_bold = desired > 500;
+    if actual < desired {
+        (if is_bold { 2 } else { 1 }, desired - actual)
+    } else {
+        (if is_bold { 1 } else { 2 }, actual - desired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_font_face_info() {
+        let data = include_bytes!("../../fonts/Roboto-Light.ttf");
+
+        assert_eq!(face_count(data), 1);
+
+        let info = FontFaceInfo::parse(data, 0).unwrap();
+        assert_eq!(info.family, "Roboto");
+        assert_eq!(info.weight, 300);
+        assert!(!info.italic);
+    }
+
+    #[test]
+    fn test_weight_distance() {
+        fn closest(desired: u16, weights: &[u16]) -> u16 {
+            *weights
+                .iter()
+                .min_by_key(|w| weight_distance(desired, **w))
+                .unwrap()
+        }
+
+        assert_eq!(closest(400, &[300, 500, 700]), 500);
+        assert_eq!(closest(400, &[300, 700]), 300);
+        assert_eq!(closest(700, &[300, 500, 900]), 900);
+        assert_eq!(closest(700, &[300, 500]), 500);
+        assert_eq!(closest(300, &[400, 200]), 200);
+        assert_eq!(closest(300, &[400, 500]), 400);
+    }
+}
@@ -1,12 +1,15 @@
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use super::char::{is_newline_as_whitespace, CharFlags, RenderingCharIndices};
 use super::font::{whitespace_width, FontMetrics};
 use super::renderer::FontSetting;
+use crate::glyph_cache::{self, GlyphBitmap, GlyphKey};
 use crate::style::KernSetting;
 use ab_glyph::{point, Glyph, GlyphId, OutlinedGlyph, Rect};
 
-pub(super) trait Font: Debug {
+// Fonts are shared between threads through FontContext.
+pub(super) trait Font: Debug + Send + Sync {
     fn glyph_id(&self, ch: char) -> GlyphId;
     fn ascent(&self, scale: f32) -> f32;
     fn descent(&self, scale: f32) -> f32;
@@ -14,6 +17,27 @@ pub(super) trait Font: Debug {
     fn kern(&self, first: GlyphId, second: GlyphId, scale: f32) -> f32;
     fn outline_glyph(&self, glyph: Glyph, scale: f32) -> Option<OutlinedGlyph>;
 
+    // Unique id of the font used as glyph cache key.
+    // Glyphs are not cached if this is `None`.
+    fn cache_id(&self) -> Option<usize> {
+        None
+    }
+
+    // Return rasterized glyph from the cache if possible.
+    fn rasterize_glyph(&self, glyph_id: GlyphId, scale: f32) -> Option<Arc<GlyphBitmap>> {
+        let rasterize = || {
+            let q_glyph: Glyph = glyph_id.with_scale_and_position(scale, point(0., 0.));
+            self.outline_glyph(q_glyph, scale)
+                .map(|q| Arc::new(GlyphBitmap::from_outline(&q)))
+        };
+        match self.cache_id() {
+            Some(id) => {
+                glyph_cache::get_or_insert_with(GlyphKey::new(id, glyph_id, scale), rasterize)
+            }
+            None => rasterize(),
+        }
+    }
+
     fn text_extents(&self, text: &str, setting: &FontSetting) -> FontMetrics {
         let mut chars = RenderingCharIndices::from_str(text);
         let mut width = 0.;
@@ -57,13 +81,13 @@ pub(super) trait Font: Debug {
         }
     }
 
+    // Bounds are read from the outline, so the glyph is not rasterized.
     fn get_glyph_rect(&self, ch: char, setting: &FontSetting) -> Option<Rect> {
-        let glyph_id = self.glyph_id(ch);
-        let q_glyph: Glyph = glyph_id.with_scale_and_position(setting.size, point(0., 0.));
-        if let Some(q) = self.outline_glyph(q_glyph, setting.size) {
-            return Some(q.px_bounds());
-        }
-        None
+        let q_glyph: Glyph = self
+            .glyph_id(ch)
+            .with_scale_and_position(setting.size, point(0., 0.));
+        self.outline_glyph(q_glyph, setting.size)
+            .map(|q| q.px_bounds())
     }
 
     fn calculate_text_width(
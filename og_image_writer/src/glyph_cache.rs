@@ -0,0 +1,355 @@
+//! Process wide cache of rasterized glyph coverage.
+//!
+//! Outlining and rasterizing glyphs is the most expensive part of rendering text.
+//! Rasterized glyphs are shared across every render and thread, keyed by font, glyph id and size.
+//! The cache is bounded by the number of glyphs and evicts the least recently used glyph when it is full.
+//!
+//! ```rust
+//! use og_image_writer::glyph_cache;
+//!
+//! glyph_cache::set_capacity(4096);
+//!
+//! let stats = glyph_cache::stats();
+//! println!("hit rate: {}", stats.hit_rate());
+//! ```
+
+use ab_glyph::{GlyphId, OutlinedGlyph, Rect};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+/// Default number of glyphs held by the cache.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+const NIL: usize = usize::MAX;
+
+/// Statistics of the glyph cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GlyphCacheStats {
+    /// Number of lookups served from the cache.
+    pub hits: u64,
+    /// Number of lookups that rasterized a glyph.
+    pub misses: u64,
+    /// Number of glyphs currently cached.
+    pub len: usize,
+    /// Maximum number of glyphs cached.
+    pub capacity: usize,
+}
+
+impl GlyphCacheStats {
+    /// Ratio of hits to all lookups. This is `0.` when nothing was looked up.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+/// Return statistics of the glyph cache.
+pub fn stats() -> GlyphCacheStats {
+    let cache = lock();
+    GlyphCacheStats {
+        hits: cache.hits,
+        misses: cache.misses,
+        len: cache.map.len(),
+        capacity: cache.capacity,
+    }
+}
+
+/// Set maximum number of cached glyphs.
+/// Least recently used glyphs are evicted when the cache shrinks. `0` disables caching.
+pub fn set_capacity(capacity: usize) {
+    let mut cache = lock();
+    cache.resize(capacity);
+}
+
+/// Remove all cached glyphs and reset statistics.
+pub fn clear() {
+    let mut cache = lock();
+    let capacity = cache.capacity;
+    *cache = GlyphCache::new(capacity);
+}
+
+/// Rasterized coverage of a glyph.
+#[derive(Debug)]
+pub(super) struct GlyphBitmap {
+    pub bounds: Rect,
+    pub width: u32,
+    coverage: Vec<f32>,
+}
+
+impl GlyphBitmap {
+    pub(super) fn from_outline(outline: &OutlinedGlyph) -> GlyphBitmap {
+        let bounds = outline.px_bounds();
+        let width = bounds.width() as u32;
+        let height = bounds.height() as u32;
+        let mut coverage = vec![0.; (width * height) as usize];
+        outline.draw(|x, y, v| {
+            coverage[(y * width + x) as usize] = v;
+        });
+        GlyphBitmap {
+            bounds,
+            width,
+            coverage,
+        }
+    }
+
+    // Call `f` with `(x, y, coverage)` for each pixel like `OutlinedGlyph::draw`.
+    pub(super) fn for_each_pixel<F: FnMut(u32, u32, f32)>(&self, mut f: F) {
+        if self.width == 0 {
+            return;
+        }
+        for (i, v) in self.coverage.iter().enumerate() {
+            let i = i as u32;
+            f(i % self.width, i / self.width, *v);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct GlyphKey {
+    font: usize,
+    glyph: u16,
+    size: u32,
+}
+
+impl GlyphKey {
+    pub(super) fn new(font: usize, glyph_id: GlyphId, size: f32) -> GlyphKey {
+        GlyphKey {
+            font,
+            glyph: glyph_id.0,
+            size: size.to_bits(),
+        }
+    }
+}
+
+type CachedGlyph = Option<Arc<GlyphBitmap>>;
+
+struct Entry {
+    key: GlyphKey,
+    value: CachedGlyph,
+    prev: usize,
+    next: usize,
+}
+
+// LRU cache backed by slab of doubly linked entries.
+struct GlyphCache {
+    map: HashMap<GlyphKey, usize>,
+    entries: Vec<Entry>,
+    // Most recently used entry.
+    head: usize,
+    // Least recently used entry.
+    tail: usize,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl GlyphCache {
+    fn new(capacity: usize) -> GlyphCache {
+        GlyphCache {
+            map: HashMap::new(),
+            entries: vec![],
+            head: NIL,
+            tail: NIL,
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn get(&mut self, key: &GlyphKey) -> Option<CachedGlyph> {
+        match self.map.get(key) {
+            Some(&idx) => {
+                self.hits += 1;
+                self.unlink(idx);
+                self.push_front(idx);
+                Some(self.entries[idx].value.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: GlyphKey, value: CachedGlyph) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some(&idx) = self.map.get(&key) {
+            self.entries[idx].value = value;
+            self.unlink(idx);
+            self.push_front(idx);
+            return;
+        }
+
+        let idx = if self.map.len() >= self.capacity {
+            let idx = self.evict();
+            self.entries[idx].key = key;
+            self.entries[idx].value = value;
+            idx
+        } else {
+            self.entries.push(Entry {
+                key,
+                value,
+                prev: NIL,
+                next: NIL,
+            });
+            self.entries.len() - 1
+        };
+        self.map.insert(key, idx);
+        self.push_front(idx);
+    }
+
+    // Keep most recently used entries up to `capacity` in a compacted slab.
+    fn resize(&mut self, capacity: usize) {
+        let mut resized = GlyphCache::new(capacity);
+        resized.hits = self.hits;
+        resized.misses = self.misses;
+
+        let mut kept = vec![];
+        let mut idx = self.head;
+        while idx != NIL && kept.len() < capacity {
+            kept.push(idx);
+            idx = self.entries[idx].next;
+        }
+        // Insert from least recently used so that order is preserved.
+        for idx in kept.into_iter().rev() {
+            let entry = &mut self.entries[idx];
+            resized.insert(entry.key, entry.value.take());
+        }
+        *self = resized;
+    }
+
+    // Remove least recently used entry and return its slot.
+    fn evict(&mut self) -> usize {
+        let idx = self.tail;
+        self.unlink(idx);
+        let entry = &mut self.entries[idx];
+        entry.value = None;
+        self.map.remove(&entry.key);
+        idx
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next) = (self.entries[idx].prev, self.entries[idx].next);
+        if prev == NIL {
+            self.head = next;
+        } else {
+            self.entries[prev].next = next;
+        }
+        if next == NIL {
+            self.tail = prev;
+        } else {
+            self.entries[next].prev = prev;
+        }
+        self.entries[idx].prev = NIL;
+        self.entries[idx].next = NIL;
+    }
+
+    fn push_front(&mut self, idx: usize) {
+        self.entries[idx].next = self.head;
+        if self.head != NIL {
+            self.entries[self.head].prev = idx;
+        }
+        self.head = idx;
+        if self.tail == NIL {
+            self.tail = idx;
+        }
+    }
+}
+
+fn lock() -> MutexGuard<'static, GlyphCache> {
+    static CACHE: OnceLock<Mutex<GlyphCache>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(GlyphCache::new(DEFAULT_CAPACITY)));
+    // Cached glyphs are immutable, so the cache is still consistent after panic.
+    cache.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Return cached glyph or rasterize it by `rasterize` and cache the result.
+// Rasterization runs outside of the lock so that other threads are not blocked.
+pub(super) fn get_or_insert_with<F>(key: GlyphKey, rasterize: F) -> CachedGlyph
+where
+    F: FnOnce() -> CachedGlyph,
+{
+    if let Some(glyph) = lock().get(&key) {
+        return glyph;
+    }
+    let glyph = rasterize();
+    lock().insert(key, glyph.clone());
+    glyph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(glyph: u16) -> GlyphKey {
+        GlyphKey::new(0, GlyphId(glyph), 16.)
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = GlyphCache::new(2);
+        cache.insert(key(1), None);
+        cache.insert(key(2), None);
+
+        // Touch 1 so that 2 becomes least recently used.
+        assert!(cache.get(&key(1)).is_some());
+        cache.insert(key(3), None);
+
+        assert!(cache.get(&key(2)).is_none());
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(3)).is_some());
+        assert_eq!(cache.map.len(), 2);
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!((cache.hits, cache.misses), (3, 1));
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut cache = GlyphCache::new(3);
+        cache.insert(key(1), None);
+        cache.insert(key(2), None);
+        cache.insert(key(3), None);
+
+        cache.resize(2);
+        assert_eq!(cache.entries.len(), 2);
+        assert!(cache.get(&key(1)).is_none());
+
+        cache.insert(key(4), None);
+        assert!(cache.get(&key(2)).is_none());
+        assert!(cache.get(&key(3)).is_some());
+        assert!(cache.get(&key(4)).is_some());
+    }
+
+    #[test]
+    fn test_cache_font_glyph() {
+        use crate::font::create_font;
+        use crate::font_trait::Font;
+
+        let font = create_font(Vec::from(
+            include_bytes!("../../fonts/Roboto-Light.ttf") as &[u8]
+        ))
+        .unwrap();
+        let glyph_id = font.glyph_id('a');
+
+        let first = font.rasterize_glyph(glyph_id, 32.).unwrap();
+        let before = stats();
+        let second = font.rasterize_glyph(glyph_id, 32.).unwrap();
+        let after = stats();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(after.hits > before.hits);
+        assert!(first.bounds.width() > 0.);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut cache = GlyphCache::new(0);
+        cache.insert(key(1), None);
+        assert!(cache.get(&key(1)).is_none());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::Error;
 
-use super::style::BorderRadius;
+use super::style::{BorderRadius, ObjectFit, ObjectPosition, Padding};
 use image::{
+    imageops::{self, FilterType},
     load_from_memory_with_format, open, DynamicImage, ImageBuffer, ImageError, ImageFormat, Rgba,
 };
 
@@ -43,12 +44,14 @@ pub(super) struct Size {
 
 pub(super) struct ImageInfo(pub(super) ImageBuffer<Rgba<u8>, Vec<u8>>, pub(super) Size);
 
-pub(super) fn open_and_resize(src: &str, w: u32, h: u32) -> Result<ImageInfo, Error> {
+pub(super) fn open_and_resize(
+    src: &str,
+    w: u32,
+    h: u32,
+    object_fit: Option<ObjectFit>,
+) -> Result<ImageInfo, Error> {
     let rgba = open(src)?.into_rgba8();
-    let buffer = DynamicImage::ImageRgba8(rgba).thumbnail(w, h).into_rgba8();
-    let height = buffer.height();
-    let width = buffer.width();
-    Ok(ImageInfo(buffer, Size { height, width }))
+    Ok(resize(rgba, w, h, object_fit))
 }
 
 pub(super) fn open_and_resize_with_data(
@@ -56,12 +59,113 @@ pub(super) fn open_and_resize_with_data(
     w: u32,
     h: u32,
     format: ImageInputFormat,
+    object_fit: Option<ObjectFit>,
 ) -> Result<ImageInfo, ImageError> {
     let rgba = load_from_memory_with_format(data, format.as_image_format())?.into_rgba8();
+    Ok(resize(rgba, w, h, object_fit))
+}
+
+// Shrink image to fit in `w` and `h` keeping aspect ratio if `object_fit` is not specified.
+// Otherwise image is kept to be fitted in the box when painted, and the box has exactly `w` and `h`.
+fn resize(
+    rgba: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    w: u32,
+    h: u32,
+    object_fit: Option<ObjectFit>,
+) -> ImageInfo {
+    if object_fit.is_some() {
+        return ImageInfo(
+            rgba,
+            Size {
+                height: h,
+                width: w,
+            },
+        );
+    }
     let buffer = DynamicImage::ImageRgba8(rgba).thumbnail(w, h).into_rgba8();
     let height = buffer.height();
     let width = buffer.width();
-    Ok(ImageInfo(buffer, Size { height, width }))
+    ImageInfo(buffer, Size { height, width })
+}
+
+// Fit image in the box of `width` and `height` by `object_fit`, and place it at `position`.
+// Part of the image outside of the box is cropped, and the box not covered by the image is transparent.
+pub(super) fn fit(
+    img: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    object_fit: ObjectFit,
+    position: &ObjectPosition,
+    width: u32,
+    height: u32,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    let (img_width, img_height) = img.dimensions();
+    if img_width == 0 || img_height == 0 {
+        return ImageBuffer::new(width, height);
+    }
+    let contain = (width as f32 / img_width as f32).min(height as f32 / img_height as f32);
+    let cover = (width as f32 / img_width as f32).max(height as f32 / img_height as f32);
+    let scale = match object_fit {
+        ObjectFit::Fill => return resize_exact(img, width, height),
+        ObjectFit::Contain => contain,
+        ObjectFit::Cover => cover,
+        ObjectFit::ScaleDown => contain.min(1.),
+        ObjectFit::None => 1.,
+    };
+    let scaled_width = ((img_width as f32 * scale).round() as u32).max(1);
+    let scaled_height = ((img_height as f32 * scale).round() as u32).max(1);
+
+    // Offset of the scaled image from the box, that is negative when image is cropped.
+    let x = ((width as f32 - scaled_width as f32) * position.0).round() as i32;
+    let y = ((height as f32 - scaled_height as f32) * position.1).round() as i32;
+    let (crop_x, crop_y) = ((-x).max(0) as u32, (-y).max(0) as u32);
+    let (box_x, box_y) = (x.max(0) as u32, y.max(0) as u32);
+    let visible_width = scaled_width
+        .saturating_sub(crop_x)
+        .min(width.saturating_sub(box_x));
+    let visible_height = scaled_height
+        .saturating_sub(crop_y)
+        .min(height.saturating_sub(box_y));
+
+    let scaled = resize_exact(img, scaled_width, scaled_height);
+    if (crop_x, crop_y, box_x, box_y) == (0, 0, 0, 0) && scaled.dimensions() == (width, height) {
+        return scaled;
+    }
+    let visible = imageops::crop_imm(&scaled, crop_x, crop_y, visible_width, visible_height);
+    let mut buf = ImageBuffer::new(width, height);
+    imageops::replace(&mut buf, &visible, box_x, box_y);
+    buf
+}
+
+// Stretch image to `width` and `height`. Image is returned as is if it already has the size.
+pub(super) fn resize_exact(
+    img: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    width: u32,
+    height: u32,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    if img.dimensions() == (width, height) {
+        return img;
+    }
+    DynamicImage::ImageRgba8(img)
+        .resize_exact(width, height, FilterType::Triangle)
+        .into_rgba8()
+}
+
+// Return radius of the box that is inside `inset` from the border box with `radius`.
+// Radius is limited so that corners of the `width` and `height` box don't overlap.
+pub(super) fn inner_radius(
+    radius: &BorderRadius,
+    inset: &Padding,
+    width: u32,
+    height: u32,
+) -> BorderRadius {
+    let Padding(top, right, bottom, left) = *inset;
+    let max = width.min(height) / 2;
+    let inner = |r: u32, a: u32, b: u32| r.saturating_sub(a.max(b)).min(max);
+    BorderRadius(
+        inner(radius.0, top, left),
+        inner(radius.1, top, right),
+        inner(radius.2, bottom, right),
+        inner(radius.3, bottom, left),
+    )
 }
 
 // See https://users.rust-lang.org/t/how-to-trim-image-to-circle-image-without-jaggy/70374
@@ -154,7 +258,7 @@ fn border_radius(
                 p += (2 * x + 2) as i32;
             } else {
                 // draw when moving to next pixel in y-direction
-                if y % 16 == 0 {
+                if y.is_multiple_of(16) {
                     draw(img, alpha, x / 16, y / 16);
                     draw(img, alpha, y / 16, x / 16);
                     skip_draw = true;
@@ -1,116 +1,481 @@
 mod container;
 mod img;
 mod split_text;
+#[cfg(feature = "taffy")]
+mod taffy_layout;
 mod text;
 mod textarea;
 
 pub(super) use split_text::SplitText;
+pub(super) use text::{layout_text, text_area_width, TextLayout};
 pub use textarea::TextArea;
 
-use super::element::Element;
-use super::style::{AlignItems, FlexDirection, JustifyContent, Margin, TextAlign};
-use super::writer::OGImageWriter;
+use super::context::Context;
+use super::element::{Element, Rect};
+use super::style::{
+    AlignContent, AlignItems, FlexDirection, FlexWrap, JustifyContent, Margin, Padding, Style,
+    TextAlign, WindowStyle,
+};
+use super::writer::{Content, OGImageWriter, Tree};
 use super::Error;
+use container::{content_size, sized_window, wrap_lines};
+#[cfg(feature = "taffy")]
+use taffy_layout::TaffyLayout;
+
+/// Algorithm that computes position and size of elements.
+/// Nested containers are laid out by the engine of the writer that is painted.
+#[derive(Debug, Default, Copy, Clone)]
+pub enum LayoutEngine {
+    /// Built-in flexbox layout.
+    #[default]
+    Builtin,
+    /// Flexbox layout that is solved by [taffy](https://docs.rs/taffy).
+    /// Text is measured by breaking lines in the space that flexbox gives.
+    /// This does not replace `Builtin`: items are moved to the alignment of `Builtin` after taffy places them,
+    /// and wrapped items are broken into lines in the same way as `Builtin`.
+    #[cfg(feature = "taffy")]
+    Taffy,
+}
 
 impl OGImageWriter {
     pub(super) fn process(&mut self) {
-        if !matches!(self.window.justify_content, JustifyContent::End) {
-            self.tree.0.reverse();
+        let mut tree = std::mem::take(&mut self.tree);
+        self.process_tree(&self.window, &mut tree);
+        self.tree = tree;
+    }
+
+    // Compute position of each element in `tree` laid out in `window`. Elements are kept in the order they were set.
+    pub(super) fn process_tree(&self, window: &WindowStyle, tree: &mut Tree) {
+        match self.layout_engine {
+            LayoutEngine::Builtin => FlexLayout::new(window, &self.content).process_tree(tree),
+            #[cfg(feature = "taffy")]
+            LayoutEngine::Taffy => TaffyLayout::new(window).process_tree(tree),
+        }
+    }
+
+    // Return window that has the size resolved by elements when it is sized by content.
+    pub(super) fn sized_window(&self) -> WindowStyle {
+        sized_window(&self.tree, &self.window)
+    }
+
+    // Resolve size of the window that is sized by content, and allocate the image in the size.
+    pub(super) fn process_size(&mut self) -> Result<(), Error> {
+        if !self.window.is_sized_by_content() {
+            return Ok(());
+        }
+
+        self.window = self.sized_window();
+        self.context = Context::new(self.window.resolved_width(), self.window.resolved_height());
+        self.process_background()
+    }
+
+    pub(super) fn process_background(&mut self) -> Result<(), Error> {
+        let window = &self.window;
+        let background_color = match &window.background_color {
+            None => return Ok(()),
+            Some(color) => color,
+        };
+
+        self.context
+            .draw_background_color(background_color.as_image_rgba())
+    }
+}
+
+// Flexbox layout of elements in window or container.
+pub(super) struct FlexLayout<'a> {
+    window: &'a WindowStyle,
+    content: &'a Content,
+    // Window that has padding, when `window` is its content box.
+    // Absolute elements that ignore window padding are placed in this.
+    padded: Option<&'a WindowStyle>,
+}
+
+impl<'a> FlexLayout<'a> {
+    pub(super) fn new(window: &'a WindowStyle, content: &'a Content) -> Self {
+        FlexLayout {
+            window,
+            content,
+            padded: None,
+        }
+    }
+
+    pub(super) fn process_tree(&self, tree: &mut Tree) {
+        // Elements are laid out inside padding, and then moved to the window position.
+        if self.window.padding_size() != (0, 0) {
+            let window = self.window.content_box();
+            let layout = FlexLayout {
+                window: &window,
+                content: self.content,
+                padded: Some(self.window),
+            };
+            layout.process_tree(tree);
+            let Padding(top, _, _, left) = self.window.padding;
+            for elm in &mut tree.0 {
+                elm.translate(left as i32, top as i32);
+            }
+            return;
+        }
+
+        match self.window.flex_wrap {
+            FlexWrap::NoWrap => self.process_line(tree),
+            FlexWrap::Wrap | FlexWrap::WrapReverse => self.process_wrap(tree),
+        }
+    }
+
+    fn process_line(&self, tree: &mut Tree) {
+        // Content size is changed when items are flexed.
+        if self.flex_items(tree) {
+            let (width, height) = content_size(tree, self.window);
+            let content = Content {
+                width: width as i32,
+                height: height as i32,
+            };
+            FlexLayout {
+                content: &content,
+                ..*self
+            }
+            .position_line(tree);
+        } else {
+            self.position_line(tree);
+        }
+    }
+
+    fn position_line(&self, tree: &mut Tree) {
+        // Reversed line is the mirror of the line that has items in reverse order and is justified to the other side.
+        if self.window.flex_direction.is_reverse() {
+            let window = unreversed(self.window);
+            tree.0.reverse();
+            FlexLayout {
+                window: &window,
+                ..*self
+            }
+            .position_line(tree);
+            tree.0.reverse();
+            return;
         }
 
+        let is_end = matches!(self.window.justify_content, JustifyContent::End);
+        if !is_end {
+            tree.0.reverse();
+        }
+
+        let count = tree.0.iter().filter(|elm| !elm.is_absolute()).count() as u32;
+        let (offset, space) = self.distribute_space(count);
+
         let mut current_y = self.calculate_logical_block() as i32;
         let mut current_x = self.calculate_logical_inline() as i32;
 
-        let mut tree = OGImageWriter::create_tree();
-        while let Some(mut elm) = self.tree.0.pop() {
+        let gap = self.window.main_gap() as i32;
+        let gap = if is_end { -gap } else { gap };
+
+        // Distributed space is rounded at each item, so that rounding error is not accumulated.
+        let mut index = 0;
+        let mut shifted = 0;
+
+        let mut processed = Tree::default();
+        while let Some(mut elm) = tree.0.pop() {
             if elm.is_absolute() {
                 self.process_absolute(&mut elm);
             } else {
-                match self.window.flex_direction {
-                    FlexDirection::Column => self.process_column_flexbox(&mut elm, &mut current_y),
-                    FlexDirection::Row => self.process_row_flexbox(&mut elm, &mut current_x),
+                let shift = (offset + space * index as f32).round() as i32;
+                let current = if self.window.flex_direction.is_column() {
+                    &mut current_y
+                } else {
+                    &mut current_x
+                };
+                *current += shift - shifted;
+                shifted = shift;
+                index += 1;
+
+                if self.window.flex_direction.is_column() {
+                    self.process_column_flexbox(&mut elm, &mut current_y);
+                    current_y += gap;
+                } else {
+                    self.process_row_flexbox(&mut elm, &mut current_x);
+                    current_x += gap;
+                }
+            }
+
+            // Children are laid out after the container is positioned.
+            if let Element::Container(Some(container)) = &mut elm {
+                container.process();
+            }
+
+            processed.0.push(elm);
+        }
+        tree.0.append(&mut processed.0);
+        // Items are processed from the end when content is justified to end.
+        if is_end {
+            tree.0.reverse();
+        }
+    }
+
+    // Resolve size of items in main axis with `flex_basis`, `flex_grow` and `flex_shrink`.
+    // Return true if any item is resized.
+    fn flex_items(&self, tree: &mut Tree) -> bool {
+        let is_column = self.window.flex_direction.is_column();
+        let mut items: Vec<&mut Element> =
+            tree.0.iter_mut().filter(|elm| !elm.is_absolute()).collect();
+        if items.is_empty() {
+            return false;
+        }
+
+        // Size and flex factors of each item in main axis.
+        let mut sizes = vec![];
+        let mut used = (self.window.main_gap() * (items.len() as u32 - 1)) as i32;
+        for elm in &items {
+            let style = match elm.style() {
+                Some(style) => style,
+                None => return false,
+            };
+            let Margin(margin_top, margin_right, margin_bottom, margin_left) = style.margin;
+            let (width, height) = elm.margin_box_size();
+            let (size, margin) = if is_column {
+                (
+                    height - margin_top - margin_bottom,
+                    margin_top + margin_bottom,
+                )
+            } else {
+                (
+                    width - margin_left - margin_right,
+                    margin_left + margin_right,
+                )
+            };
+            let (inset_width, inset_height) = style.inset_size();
+            let inset = if is_column { inset_height } else { inset_width };
+            let basis = style
+                .flex_basis
+                .map_or(size, |basis| style.border_box(basis, inset) as i32);
+            used += basis + margin;
+            sizes.push((size, basis, style.flex_grow, style.flex_shrink));
+        }
+
+        let main_size = if is_column {
+            self.window.resolved_height()
+        } else {
+            self.window.resolved_width()
+        } as i32;
+        let free = (main_size - used) as f32;
+        let total_grow: f32 = sizes.iter().map(|(_, _, grow, _)| grow).sum();
+        // Items shrink in proportion to their basis like CSS.
+        let total_shrink: f32 = sizes
+            .iter()
+            .map(|(_, basis, _, shrink)| shrink * *basis as f32)
+            .sum();
+
+        let mut is_resized = false;
+        for (elm, (size, basis, grow, shrink)) in items.iter_mut().zip(sizes) {
+            let flexed = if free > 0. && total_grow > 0. {
+                basis + (free * grow / total_grow) as i32
+            } else if free < 0. && total_shrink > 0. {
+                basis + (free * shrink * basis as f32 / total_shrink) as i32
+            } else {
+                basis
+            }
+            .max(0);
+            if flexed != size {
+                resize_item(elm, flexed as u32, is_column);
+                is_resized = true;
+            }
+        }
+        is_resized
+    }
+
+    // Break items into lines, and lay out each line as a window that has the size of the line in cross axis.
+    fn process_wrap(&self, tree: &mut Tree) {
+        let is_column = self.window.flex_direction.is_column();
+        let cross_size = if is_column {
+            self.window.resolved_width()
+        } else {
+            self.window.resolved_height()
+        } as i32;
+
+        let lines = wrap_lines(tree, self.window);
+        let mut slots: Vec<Option<Element>> = vec![];
+        for mut elm in tree.0.drain(..) {
+            if elm.is_absolute() {
+                self.process_absolute(&mut elm);
+            }
+            slots.push(Some(elm));
+        }
+
+        let mut line_trees: Vec<(Vec<usize>, Tree, u32, u32)> = lines
+            .into_iter()
+            .map(|indexes| {
+                let line = Tree(indexes.iter().filter_map(|&i| slots[i].take()).collect());
+                let (width, height) = content_size(&line, self.window);
+                (indexes, line, width, height)
+            })
+            .collect();
+
+        let line_crosses: Vec<i32> = line_trees
+            .iter()
+            .map(|(_, _, width, height)| if is_column { *width } else { *height } as i32)
+            .collect();
+        let positions = self.calculate_line_positions(&line_crosses, cross_size);
+
+        for ((indexes, line, width, height), (offset, cross)) in
+            line_trees.iter_mut().zip(positions)
+        {
+            let window = WindowStyle {
+                width: if is_column {
+                    Some(cross as u32)
+                } else {
+                    self.window.width
+                },
+                height: if is_column {
+                    self.window.height
+                } else {
+                    Some(cross as u32)
+                },
+                flex_wrap: FlexWrap::NoWrap,
+                ..self.window.clone()
+            };
+            let content = Content {
+                width: *width as i32,
+                height: *height as i32,
+            };
+            FlexLayout::new(&window, &content).process_line(line);
+
+            for (&i, mut elm) in indexes.iter().zip(line.0.drain(..)) {
+                if is_column {
+                    elm.translate(offset, 0);
+                } else {
+                    elm.translate(0, offset);
                 }
+                slots[i] = Some(elm);
+            }
+        }
+
+        tree.0.extend(slots.into_iter().flatten());
+    }
+
+    // Return offset and size of each line in cross axis.
+    fn calculate_line_positions(&self, crosses: &[i32], cross_size: i32) -> Vec<(i32, i32)> {
+        if crosses.is_empty() {
+            return vec![];
+        }
+        let count = crosses.len() as i32;
+        let gap = self.window.cross_gap() as i32;
+        let free = (cross_size - crosses.iter().sum::<i32>() - gap * (count - 1)).max(0);
+
+        let (mut offset, space, stretch) = match self.window.align_content {
+            AlignContent::Start => (0, 0, 0),
+            AlignContent::Center => (free / 2, 0, 0),
+            AlignContent::End => (free, 0, 0),
+            AlignContent::SpaceBetween if count > 1 => (0, free / (count - 1), 0),
+            AlignContent::SpaceBetween => (0, 0, 0),
+            AlignContent::SpaceAround => (free / count / 2, free / count, 0),
+            AlignContent::Stretch => (0, 0, free / count),
+        };
+
+        let mut positions = vec![];
+        for cross in crosses {
+            let cross = cross + stretch;
+            positions.push((offset, cross));
+            offset += cross + space + gap;
+        }
+
+        // Lines are stacked from the end of cross axis.
+        if matches!(self.window.flex_wrap, FlexWrap::WrapReverse) {
+            for (offset, cross) in &mut positions {
+                *offset = cross_size - *offset - *cross;
             }
+        }
+        positions
+    }
+
+    // Return offset of the first item and space between items for distributed `justify_content`.
+    fn distribute_space(&self, count: u32) -> (f32, f32) {
+        let free = if self.window.flex_direction.is_column() {
+            self.window
+                .resolved_height()
+                .saturating_sub(self.content.height())
+        } else {
+            self.window
+                .resolved_width()
+                .saturating_sub(self.content.width())
+        } as f32;
+        let count = count as f32;
 
-            tree.0.push(elm);
+        match self.window.justify_content {
+            JustifyContent::SpaceBetween if count > 1. => (0., free / (count - 1.)),
+            JustifyContent::SpaceAround if count > 0. => (free / count / 2., free / count),
+            JustifyContent::SpaceEvenly => (free / (count + 1.), free / (count + 1.)),
+            _ => (0., 0.),
         }
-        self.tree.0.append(&mut tree.0);
     }
 
+    // Offset of distributed items is added to each item in `position_line`.
     fn calculate_logical_block(&self) -> u32 {
-        let total_height = if self.content.height > self.window.height {
-            self.window.height
+        let total_height = if self.content.height() > self.window.resolved_height() {
+            self.window.resolved_height()
         } else {
-            self.content.height
+            self.content.height()
         };
 
         let center_height = total_height / 2;
-        let rest_height = self.window.height / 2 - center_height;
+        let rest_height = self.window.resolved_height() / 2 - center_height;
 
-        let window_height = self.window.height;
+        let window_height = self.window.resolved_height();
 
         match &self.window.justify_content {
             JustifyContent::Start => 0,
             JustifyContent::Center => rest_height,
             JustifyContent::End => window_height,
+            JustifyContent::SpaceBetween
+            | JustifyContent::SpaceAround
+            | JustifyContent::SpaceEvenly => 0,
         }
     }
 
     fn calculate_logical_inline(&self) -> u32 {
-        let total_width = if self.content.width > self.window.width {
-            self.window.width
+        let total_width = if self.content.width() > self.window.resolved_width() {
+            self.window.resolved_width()
         } else {
-            self.content.width
+            self.content.width()
         };
 
         let center_width = total_width / 2;
-        let rest_width = self.window.width / 2 - center_width;
+        let rest_width = self.window.resolved_width() / 2 - center_width;
 
-        let window_width = self.window.width;
+        let window_width = self.window.resolved_width();
 
         match &self.window.justify_content {
             JustifyContent::Start => 0,
             JustifyContent::Center => rest_width,
             JustifyContent::End => window_width,
+            JustifyContent::SpaceBetween
+            | JustifyContent::SpaceAround
+            | JustifyContent::SpaceEvenly => 0,
         }
     }
 
-    fn process_column_flexbox(&mut self, elm: &mut Element, current_y: &mut i32) {
-        let window_width = self.window.width as i32;
+    fn align_self(&self, style: &Style) -> AlignItems {
+        style.align_self.unwrap_or(self.window.align_items)
+    }
+
+    fn process_column_flexbox(&self, elm: &mut Element, current_y: &mut i32) {
+        let window_width = self.window.resolved_width() as i32;
         let is_end = matches!(self.window.justify_content, JustifyContent::End);
         match elm {
             Element::Img(Some(img)) => {
-                let Margin(margin_top, margin_right, margin_bottom, margin_left) = img.style.margin;
-
-                let logical_inline = match &self.window.align_items {
-                    AlignItems::Start => margin_left,
-                    AlignItems::Center => {
-                        window_width / 2 - img.rect.width as i32 / 2 + margin_left - margin_right
-                    }
-                    AlignItems::End => window_width - img.rect.width as i32 - margin_right,
-                };
-
-                img.rect.x = logical_inline as u32;
-
-                if is_end {
-                    img.rect.y += (*current_y - img.rect.height as i32 - margin_bottom) as u32;
-                    *current_y -= img.rect.height as i32 + margin_top + margin_bottom;
-                } else {
-                    img.rect.y += (*current_y + margin_top) as u32;
-                    *current_y += img.rect.height as i32 + margin_top + margin_bottom;
-                }
+                self.process_column_box(&mut img.rect, &img.style, current_y)
+            }
+            Element::Container(Some(container)) => {
+                self.process_column_box(&mut container.rect, &container.style, current_y)
             }
             Element::Text(Some(text)) => {
                 let Margin(margin_top, margin_right, margin_bottom, margin_left) =
-                    text.style.margin;
+                    text.style.spacing();
 
                 let line_metrics = &text.metrics;
 
                 // Because ab_glyph draw text that include line_height.
-                let mut system_line_height = line_metrics.max_line_height as u32 / 2;
+                let mut system_line_height = line_metrics.max_line_height as i32 / 2;
 
                 for fragment in &mut text.fragments {
-                    let logical_inline = match &self.window.align_items {
+                    let logical_inline = match self.align_self(&text.style) {
                         AlignItems::Start => margin_left,
                         AlignItems::Center => {
                             window_width / 2 - line_metrics.max_line_width as i32 / 2 + margin_left
@@ -131,22 +496,20 @@ impl OGImageWriter {
                         }
                     } + logical_inline;
 
-                    fragment.rect.x += content_box_inline as u32;
+                    fragment.rect.x += content_box_inline;
                     if is_end {
-                        fragment.rect.y +=
-                            (*current_y - line_metrics.total_height as i32 - margin_bottom) as u32
-                                - system_line_height;
+                        fragment.rect.y += *current_y
+                            - line_metrics.total_height as i32
+                            - margin_bottom
+                            - system_line_height;
                     } else {
-                        fragment.rect.y += (*current_y + margin_top) as u32;
+                        fragment.rect.y += *current_y + margin_top;
                     }
 
-                    if matches!(self.window.justify_content, JustifyContent::Center) {
-                        if fragment.rect.y >= system_line_height {
-                            fragment.rect.y -= system_line_height;
-                        } else {
-                            fragment.rect.y = 0;
-                            system_line_height = 0;
-                        }
+                    if matches!(self.window.justify_content, JustifyContent::Center)
+                        || self.window.justify_content.is_distributed()
+                    {
+                        shift_line_height(&mut fragment.rect, &mut system_line_height);
                     }
                 }
 
@@ -155,7 +518,7 @@ impl OGImageWriter {
                 } else {
                     *current_y += line_metrics.total_height as i32 + margin_top + margin_bottom;
                     if matches!(self.window.justify_content, JustifyContent::Start) {
-                        *current_y += system_line_height as i32;
+                        *current_y += system_line_height;
                     }
                 }
             }
@@ -163,56 +526,63 @@ impl OGImageWriter {
         }
     }
 
-    fn process_row_flexbox(&mut self, elm: &mut Element, current_x: &mut i32) {
-        let window_height = self.window.height as i32;
+    fn process_column_box(&self, rect: &mut Rect, style: &Style, current_y: &mut i32) {
+        let window_width = self.window.resolved_width() as i32;
         let is_end = matches!(self.window.justify_content, JustifyContent::End);
-        match elm {
-            Element::Img(Some(img)) => {
-                let Margin(margin_top, margin_right, margin_bottom, margin_left) = img.style.margin;
+        let Margin(margin_top, margin_right, margin_bottom, margin_left) = style.margin;
 
-                let logical_block = match &self.window.align_items {
-                    AlignItems::Start => margin_top,
-                    AlignItems::Center => {
-                        window_height / 2 - img.rect.height as i32 / 2 + margin_top - margin_bottom
-                    }
-                    AlignItems::End => window_height - img.rect.height as i32 - margin_bottom,
-                };
+        let logical_inline = match self.align_self(style) {
+            AlignItems::Start => margin_left,
+            AlignItems::Center => {
+                window_width / 2 - rect.width as i32 / 2 + margin_left - margin_right
+            }
+            AlignItems::End => window_width - rect.width as i32 - margin_right,
+        };
 
-                img.rect.y = logical_block as u32;
+        rect.x = logical_inline;
 
-                if is_end {
-                    img.rect.x += (*current_x - img.rect.width as i32 - margin_right) as u32;
-                    *current_x -= img.rect.width as i32 + margin_left + margin_right;
-                } else {
-                    img.rect.x += (*current_x + margin_left) as u32;
-                    *current_x += img.rect.width as i32 + margin_left + margin_right;
-                }
+        if is_end {
+            rect.y += *current_y - rect.height as i32 - margin_bottom;
+            *current_y -= rect.height as i32 + margin_top + margin_bottom;
+        } else {
+            rect.y += *current_y + margin_top;
+            *current_y += rect.height as i32 + margin_top + margin_bottom;
+        }
+    }
+
+    fn process_row_flexbox(&self, elm: &mut Element, current_x: &mut i32) {
+        let window_height = self.window.resolved_height() as i32;
+        let is_end = matches!(self.window.justify_content, JustifyContent::End);
+        match elm {
+            Element::Img(Some(img)) => self.process_row_box(&mut img.rect, &img.style, current_x),
+            Element::Container(Some(container)) => {
+                self.process_row_box(&mut container.rect, &container.style, current_x)
             }
             Element::Text(Some(text)) => {
                 let Margin(margin_top, margin_right, margin_bottom, margin_left) =
-                    text.style.margin;
+                    text.style.spacing();
 
                 let line_metrics = &text.metrics;
 
                 // Because ab_glyph draw text that include line_height.
-                let mut system_line_height = line_metrics.max_line_height as u32 / 2;
+                let mut system_line_height = line_metrics.max_line_height as i32 / 2;
 
                 for fragment in &mut text.fragments {
-                    let logical_block = match &self.window.align_items {
+                    let logical_block = match self.align_self(&text.style) {
                         AlignItems::Start => margin_top,
                         AlignItems::Center => {
                             window_height / 2 - line_metrics.total_height as i32 / 2 + margin_top
                                 - margin_bottom
                         }
                         AlignItems::End => {
-                            self.window.height as i32
+                            self.window.resolved_height() as i32
                                 - line_metrics.total_height as i32
-                                - system_line_height as i32
+                                - system_line_height
                                 - margin_bottom
                         }
                     };
 
-                    fragment.rect.y += logical_block as u32;
+                    fragment.rect.y += logical_block;
 
                     fragment.rect.x += match text.style.text_align {
                         TextAlign::Start => 0,
@@ -222,22 +592,17 @@ impl OGImageWriter {
                         TextAlign::End => {
                             line_metrics.max_line_width as i32 - fragment.rect.width as i32
                         }
-                    } as u32;
+                    };
 
                     if is_end {
                         fragment.rect.x +=
-                            (*current_x - line_metrics.max_line_width as i32 - margin_right) as u32;
+                            *current_x - line_metrics.max_line_width as i32 - margin_right;
                     } else {
-                        fragment.rect.x += (*current_x + margin_left) as u32;
+                        fragment.rect.x += *current_x + margin_left;
                     }
 
-                    if matches!(self.window.align_items, AlignItems::Center) {
-                        if fragment.rect.y >= system_line_height {
-                            fragment.rect.y -= system_line_height;
-                        } else {
-                            fragment.rect.y = 0;
-                            system_line_height = 0;
-                        }
+                    if matches!(self.align_self(&text.style), AlignItems::Center) {
+                        shift_line_height(&mut fragment.rect, &mut system_line_height);
                     }
                 }
 
@@ -251,70 +616,422 @@ impl OGImageWriter {
         }
     }
 
-    fn process_absolute(&mut self, elm: &mut Element) {
-        match elm {
-            Element::Img(Some(img)) => {
-                let Margin(margin_top, margin_right, margin_bottom, margin_left) = img.style.margin;
+    fn process_row_box(&self, rect: &mut Rect, style: &Style, current_x: &mut i32) {
+        let window_height = self.window.resolved_height() as i32;
+        let is_end = matches!(self.window.justify_content, JustifyContent::End);
+        let Margin(margin_top, margin_right, margin_bottom, margin_left) = style.margin;
 
-                img.rect.x += match (img.style.left, img.style.right) {
-                    (Some(left), _) => left as i32 + margin_left,
-                    (None, Some(right)) => {
-                        self.window.width as i32 - img.rect.width as i32 - right - margin_right
-                    }
-                    (None, None) => margin_left,
-                } as u32;
-                img.rect.y += match (img.style.top, img.style.bottom) {
-                    (Some(top), _) => top + margin_top,
-                    (None, Some(bottom)) => {
-                        self.window.height as i32 - img.rect.height as i32 - bottom - margin_bottom
-                    }
-                    (None, None) => margin_top,
-                } as u32;
+        let logical_block = match self.align_self(style) {
+            AlignItems::Start => margin_top,
+            AlignItems::Center => {
+                window_height / 2 - rect.height as i32 / 2 + margin_top - margin_bottom
+            }
+            AlignItems::End => window_height - rect.height as i32 - margin_bottom,
+        };
+
+        rect.y = logical_block;
+
+        if is_end {
+            rect.x += *current_x - rect.width as i32 - margin_right;
+            *current_x -= rect.width as i32 + margin_left + margin_right;
+        } else {
+            rect.x += *current_x + margin_left;
+            *current_x += rect.width as i32 + margin_left + margin_right;
+        }
+    }
+
+    // Return window that absolute element with `style` is placed in, and offset from this layout.
+    fn containing_block(&self, style: &Style) -> (&WindowStyle, i32, i32) {
+        match self.padded {
+            Some(window) if style.ignore_window_padding => {
+                let Padding(top, _, _, left) = window.padding;
+                (window, -(left as i32), -(top as i32))
+            }
+            _ => (self.window, 0, 0),
+        }
+    }
+
+    fn process_absolute(&self, elm: &mut Element) {
+        match elm {
+            Element::Img(Some(img)) => self.process_absolute_box(&mut img.rect, &img.style),
+            Element::Container(Some(container)) => {
+                self.process_absolute_box(&mut container.rect, &container.style)
             }
             Element::Text(Some(text)) => {
                 let Margin(margin_top, margin_right, margin_bottom, margin_left) =
-                    text.style.margin;
+                    text.style.spacing();
 
                 let line_metrics = &text.metrics;
+                let (window, dx, dy) = self.containing_block(&text.style);
 
                 for fragment in &mut text.fragments {
-                    fragment.rect.x += match (text.style.left, text.style.right) {
-                        (Some(left), _) => left + margin_left,
-                        (None, Some(right)) => {
-                            self.window.width as i32
-                                - line_metrics.max_line_width as i32
-                                - right
-                                - margin_right
-                        }
-                        (None, None) => margin_left,
-                    } as u32;
-                    fragment.rect.y += match (text.style.top, text.style.bottom) {
-                        (Some(top), _) => top + margin_top,
-                        (None, Some(bottom)) => {
-                            let system_line_height = line_metrics.max_line_height as u32 / 2;
-
-                            self.window.height as i32
-                                - system_line_height as i32
-                                - line_metrics.total_height as i32
-                                - bottom
-                                - margin_bottom
-                        }
-                        (None, None) => margin_top,
-                    } as u32;
+                    fragment.rect.x += dx
+                        + match (text.style.left, text.style.right) {
+                            (Some(left), _) => left + margin_left,
+                            (None, Some(right)) => {
+                                window.resolved_width() as i32
+                                    - line_metrics.max_line_width as i32
+                                    - right
+                                    - margin_right
+                            }
+                            (None, None) => margin_left,
+                        };
+                    fragment.rect.y += dy
+                        + match (text.style.top, text.style.bottom) {
+                            (Some(top), _) => top + margin_top,
+                            (None, Some(bottom)) => {
+                                let system_line_height = line_metrics.max_line_height as i32 / 2;
+
+                                window.resolved_height() as i32
+                                    - system_line_height
+                                    - line_metrics.total_height as i32
+                                    - bottom
+                                    - margin_bottom
+                            }
+                            (None, None) => margin_top,
+                        };
                 }
             }
             _ => {}
         }
     }
 
-    pub(super) fn process_background(&mut self) -> Result<(), Error> {
-        let window = &self.window;
-        let background_color = match &window.background_color {
-            None => return Ok(()),
-            Some(color) => color,
+    fn process_absolute_box(&self, rect: &mut Rect, style: &Style) {
+        let Margin(margin_top, margin_right, margin_bottom, margin_left) = style.margin;
+        let (window, dx, dy) = self.containing_block(style);
+
+        rect.x += dx
+            + match (style.left, style.right) {
+                (Some(left), _) => left + margin_left,
+                (None, Some(right)) => {
+                    window.resolved_width() as i32 - rect.width as i32 - right - margin_right
+                }
+                (None, None) => margin_left,
+            };
+        rect.y += dy
+            + match (style.top, style.bottom) {
+                (Some(top), _) => top + margin_top,
+                (None, Some(bottom)) => {
+                    window.resolved_height() as i32 - rect.height as i32 - bottom - margin_bottom
+                }
+                (None, None) => margin_top,
+            };
+    }
+}
+
+// Move line up by `system_line_height`.
+// Line that would cross the top edge is put on the edge, and following lines are not moved.
+// Line that is already above the edge, for example by negative margin, is moved as is.
+fn shift_line_height(rect: &mut Rect, system_line_height: &mut i32) {
+    if rect.y >= *system_line_height || rect.y < 0 {
+        rect.y -= *system_line_height;
+    } else {
+        rect.y = 0;
+        *system_line_height = 0;
+    }
+}
+
+// Return window that has the forward direction of `window`, and swapped `Start` and `End` of `justify_content`.
+fn unreversed(window: &WindowStyle) -> WindowStyle {
+    let flex_direction = if window.flex_direction.is_column() {
+        FlexDirection::Column
+    } else {
+        FlexDirection::Row
+    };
+    let justify_content = match window.justify_content {
+        JustifyContent::Start => JustifyContent::End,
+        JustifyContent::End => JustifyContent::Start,
+        justify_content => justify_content,
+    };
+    WindowStyle {
+        flex_direction,
+        justify_content,
+        ..window.clone()
+    }
+}
+
+// Resize border box of element in main axis.
+fn resize_item(elm: &mut Element, size: u32, is_column: bool) {
+    let (inset_width, inset_height) = match elm.style() {
+        Some(style) => style.inset_size(),
+        None => return,
+    };
+    match elm {
+        Element::Img(Some(img)) => {
+            if is_column {
+                img.rect.height = size;
+            } else {
+                img.rect.width = size;
+            }
+        }
+        Element::Text(Some(text)) => {
+            if is_column {
+                text.metrics.total_height = size.saturating_sub(inset_height);
+            } else {
+                text.set_width(size.saturating_sub(inset_width));
+            }
+        }
+        Element::Container(Some(container)) => {
+            if is_column {
+                container.rect.height = size;
+                container.window.height = Some(size.saturating_sub(inset_height));
+            } else {
+                container.rect.width = size;
+                container.window.width = Some(size.saturating_sub(inset_width));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::img::ImageInputFormat;
+    use crate::layout_tree::test_utils::{layout_images, ROBOTO, THUMBNAIL};
+    use crate::style::Position;
+
+    fn positions(window: WindowStyle, styles: &[Style]) -> Vec<(i32, i32)> {
+        layout_images(window, styles)
+            .iter()
+            .map(|rect| (rect.x, rect.y))
+            .collect()
+    }
+
+    #[test]
+    fn test_layout_wrap() {
+        let window = |flex_wrap| WindowStyle {
+            width: Some(250),
+            height: Some(512),
+            flex_direction: FlexDirection::Row,
+            flex_wrap,
+            align_content: AlignContent::Start,
+            ..WindowStyle::default()
         };
+        let styles = vec![Style::default(); 5];
 
-        self.context
-            .draw_background_color(background_color.as_image_rgba())
+        assert_eq!(
+            positions(window(FlexWrap::Wrap), &styles),
+            vec![(0, 0), (100, 0), (0, 100), (100, 100), (0, 200)]
+        );
+        assert_eq!(
+            positions(window(FlexWrap::WrapReverse), &styles),
+            vec![(0, 412), (100, 412), (0, 312), (100, 312), (0, 212)]
+        );
+        assert_eq!(positions(window(FlexWrap::NoWrap), &styles)[4], (400, 0));
+    }
+
+    #[test]
+    fn test_layout_gap() {
+        let styles = vec![Style::default(); 3];
+
+        let centered = WindowStyle {
+            width: Some(1000),
+            height: Some(512),
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::Center,
+            gap: 20,
+            ..WindowStyle::default()
+        };
+        assert_eq!(
+            positions(centered, &styles),
+            vec![(330, 0), (450, 0), (570, 0)]
+        );
+
+        let wrapped = WindowStyle {
+            width: Some(250),
+            height: Some(512),
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            align_content: AlignContent::Start,
+            gap: 20,
+            row_gap: Some(10),
+            ..WindowStyle::default()
+        };
+        assert_eq!(
+            positions(wrapped, &styles),
+            vec![(0, 0), (120, 0), (0, 110)]
+        );
+    }
+
+    #[test]
+    fn test_layout_distribute() {
+        let xs = |justify_content| {
+            let window = WindowStyle {
+                width: Some(1000),
+                height: Some(512),
+                flex_direction: FlexDirection::Row,
+                justify_content,
+                ..WindowStyle::default()
+            };
+            layout_images(window, &vec![Style::default(); 3])
+                .iter()
+                .map(|rect| rect.x)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(xs(JustifyContent::SpaceBetween), vec![0, 450, 900]);
+        // Rounding error is not accumulated, so both ends have the same space.
+        assert_eq!(xs(JustifyContent::SpaceAround), vec![117, 450, 783]);
+        assert_eq!(xs(JustifyContent::SpaceEvenly), vec![175, 450, 725]);
+    }
+
+    #[test]
+    fn test_layout_flex() {
+        let spans = |styles: &[Style]| {
+            let window = WindowStyle {
+                width: Some(1000),
+                height: Some(512),
+                flex_direction: FlexDirection::Row,
+                ..WindowStyle::default()
+            };
+            layout_images(window, styles)
+                .iter()
+                .map(|rect| (rect.x, rect.width))
+                .collect::<Vec<_>>()
+        };
+
+        let grow = Style {
+            flex_grow: 1.,
+            ..Style::default()
+        };
+        assert_eq!(spans(&[grow, Style::default()]), vec![(0, 900), (900, 100)]);
+
+        let shrink = Style {
+            flex_shrink: 1.,
+            flex_basis: Some(700),
+            ..Style::default()
+        };
+        assert_eq!(spans(&[shrink.clone(), shrink]), vec![(0, 500), (500, 500)]);
+    }
+
+    #[test]
+    fn test_layout_flex_direction_reverse() {
+        let layout = |flex_direction, justify_content| {
+            let window = WindowStyle {
+                width: Some(1000),
+                height: Some(500),
+                flex_direction,
+                justify_content,
+                gap: 10,
+                ..WindowStyle::default()
+            };
+            let styles = [100, 50].map(|size| Style {
+                margin: Margin(0, 5, 0, 5),
+                width: Some(size),
+                height: Some(size),
+                ..Style::default()
+            });
+            positions(window, &styles)
+        };
+
+        // The first item is placed at the end of main axis.
+        let start = layout(FlexDirection::RowReverse, JustifyContent::Start);
+        assert_eq!(start, vec![(895, 0), (825, 0)]);
+        let end = layout(FlexDirection::RowReverse, JustifyContent::End);
+        assert_eq!(end, vec![(75, 0), (5, 0)]);
+        let center = layout(FlexDirection::RowReverse, JustifyContent::Center);
+        assert_eq!(center, vec![(485, 0), (415, 0)]);
+
+        let start = layout(FlexDirection::ColumnReverse, JustifyContent::Start);
+        assert_eq!(start, vec![(5, 400), (5, 340)]);
+        let end = layout(FlexDirection::ColumnReverse, JustifyContent::End);
+        assert_eq!(end, vec![(5, 60), (5, 0)]);
+    }
+
+    #[test]
+    fn test_layout_negative_position() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer
+            .set_img_with_data(
+                THUMBNAIL,
+                100,
+                100,
+                ImageInputFormat::Png,
+                Style {
+                    position: Position::Absolute,
+                    top: Some(-30),
+                    left: Some(-50),
+                    ..Style::default()
+                },
+            )
+            .unwrap();
+        writer
+            .set_text(
+                "Hello",
+                Style {
+                    margin: Margin(-20, 0, 0, -10),
+                    ..Style::default()
+                },
+                Some(ROBOTO.to_vec()),
+            )
+            .unwrap();
+        let layout = writer.layout();
+
+        let img = &layout.children[0].rect;
+        assert_eq!((img.x, img.y), (-50, -30));
+        let text = &layout.children[1].fragments[0].rect;
+        assert_eq!((text.x, text.y), (-10, -20));
+
+        // Element that is partially off the window is painted.
+        writer.paint().unwrap();
+    }
+
+    #[test]
+    fn test_layout_margin_larger_than_element() {
+        let window = |flex_direction| WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            flex_direction,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Start,
+            ..WindowStyle::default()
+        };
+        // Content size is clamped at `0`, so the element is positioned from the center.
+        let column = positions(
+            window(FlexDirection::Column),
+            &[Style {
+                margin: Margin(-200, 0, 0, 0),
+                ..Style::default()
+            }],
+        );
+        assert_eq!(column, vec![(0, 56)]);
+        let row = positions(
+            window(FlexDirection::Row),
+            &[Style {
+                margin: Margin(0, 0, 0, -200),
+                ..Style::default()
+            }],
+        );
+        assert_eq!(row, vec![(312, 0)]);
+    }
+
+    #[test]
+    fn test_layout_window_padding() {
+        let window = WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            padding: Padding(40, 60, 40, 60),
+            ..WindowStyle::default()
+        };
+        let absolute = |ignore_window_padding| Style {
+            position: Position::Absolute,
+            bottom: Some(0),
+            right: Some(0),
+            ignore_window_padding,
+            ..Style::default()
+        };
+        let styles = [Style::default(), absolute(false), absolute(true)];
+
+        // Absolute element that ignores window padding is placed at the edge of the window.
+        assert_eq!(
+            positions(window, &styles),
+            vec![(60, 40), (864, 372), (924, 412)]
+        );
     }
 }
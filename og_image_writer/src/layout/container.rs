@@ -1,8 +1,185 @@
-use crate::element::{Element, Img, Rect};
-use crate::img::round;
-use crate::style::Style;
-use crate::writer::OGImageWriter;
+use super::{unreversed, FlexLayout};
+use crate::element::{Container, Element, Rect};
+use crate::img::{inner_radius, resize_exact, round};
+use crate::style::{FlexWrap, JustifyContent, Padding, Style, WindowStyle};
+use crate::writer::{OGImageWriter, Tree};
 use crate::Error;
+use image::RgbaImage;
+use std::mem;
+
+impl Container {
+    // Return background in the size of the padding box, and the padding box.
+    // Background is created again from `background_color` when the container is flexed.
+    pub(crate) fn take_background(&mut self) -> Option<(RgbaImage, Rect)> {
+        let background = self.background.take()?;
+        let border = self.style.border_width;
+        let rect = self.rect.shrink(&Padding(border, border, border, border));
+        let (width, height) = (rect.width, rect.height);
+        let mut background = if background.dimensions() == (width, height) {
+            background
+        } else {
+            match self.window.background_color {
+                Some(color) => RgbaImage::from_pixel(width, height, color.as_image_rgba()),
+                None => resize_exact(background, width, height),
+            }
+        };
+        let border = Padding(border, border, border, border);
+        round(
+            &mut background,
+            &mut inner_radius(&self.style.border_radius, &border, width, height),
+        );
+        Some((background, rect))
+    }
+
+    // Lay out children in the container, and then move them to the container position.
+    pub(crate) fn process(&mut self) {
+        FlexLayout::new(&self.window, &self.content).process_tree(&mut self.tree);
+        let Padding(top, _, _, left) = self.style.inset();
+        for elm in &mut self.tree.0 {
+            elm.translate(self.rect.x + left as i32, self.rect.y + top as i32);
+        }
+    }
+}
+
+// Break items in `tree` into lines that fit in the main size of `window`, and return index of items in `tree` for each line.
+// Window that is sized by content wraps items in its max size, and items are not wrapped when the size is not bounded.
+// Absolute elements are not in any line.
+pub(crate) fn wrap_lines(tree: &Tree, window: &WindowStyle) -> Vec<Vec<usize>> {
+    let is_column = window.flex_direction.is_column();
+    let items = tree
+        .0
+        .iter()
+        .enumerate()
+        .filter(|(_, elm)| !elm.is_absolute());
+    if matches!(window.flex_wrap, FlexWrap::NoWrap) {
+        let line: Vec<usize> = items.map(|(i, _)| i).collect();
+        return if line.is_empty() { vec![] } else { vec![line] };
+    }
+
+    let (padding_width, padding_height) = window.padding_size();
+    let main_size = if is_column {
+        window
+            .height
+            .or(window.max_height)
+            .map(|height| height.saturating_sub(padding_height))
+    } else {
+        window
+            .width
+            .or(window.max_width)
+            .map(|width| width.saturating_sub(padding_width))
+    }
+    .map_or(i32::MAX, |size| size as i32);
+    let gap = window.main_gap() as i32;
+
+    let mut lines: Vec<Vec<usize>> = vec![];
+    let mut line_main = 0;
+    for (i, elm) in items {
+        let (width, height) = elm.margin_box_size();
+        let item_main = if is_column { height } else { width };
+        match lines.last_mut() {
+            Some(line) if line_main + gap + item_main <= main_size => {
+                line.push(i);
+                line_main += gap + item_main;
+            }
+            _ => {
+                lines.push(vec![i]);
+                line_main = item_main;
+            }
+        }
+    }
+    lines
+}
+
+// Return size of the content that is laid out in `window`.
+// Items and gaps between them are summed in main axis, and the largest item is used in cross axis.
+// When items are wrapped, the longest line is used in main axis, and lines and gaps between them are summed in cross axis.
+pub(crate) fn content_size(tree: &Tree, window: &WindowStyle) -> (u32, u32) {
+    lines_size(tree, window, |elm, _| elm.margin_box_size())
+}
+
+// Return size of lines of items in `tree` that have size of `item_size`.
+// `item_size` is also given whether the item is at the end of its line in main axis.
+fn lines_size(
+    tree: &Tree,
+    window: &WindowStyle,
+    item_size: impl Fn(&Element, bool) -> (i32, i32),
+) -> (u32, u32) {
+    let is_column = window.flex_direction.is_column();
+    let (mut main, mut cross) = (0, 0);
+    for (i, line) in wrap_lines(tree, window).iter().enumerate() {
+        let (mut line_main, mut line_cross) = (0, 0);
+        for (j, &index) in line.iter().enumerate() {
+            let is_end = if window.flex_direction.is_reverse() {
+                j == 0
+            } else {
+                j == line.len() - 1
+            };
+            let (width, height) = item_size(&tree.0[index], is_end);
+            let (item_main, item_cross) = if is_column {
+                (height, width)
+            } else {
+                (width, height)
+            };
+            if j > 0 {
+                line_main += window.main_gap() as i32;
+            }
+            line_main += item_main;
+            line_cross = line_cross.max(item_cross);
+        }
+        if i > 0 {
+            cross += window.cross_gap() as i32;
+        }
+        main = main.max(line_main);
+        cross += line_cross;
+    }
+    let (main, cross) = (main.max(0) as u32, cross.max(0) as u32);
+    if is_column {
+        (cross, main)
+    } else {
+        (main, cross)
+    }
+}
+
+// Return size of the margin box of element that is extended to the space it takes when painted in `window`.
+// Text is followed by half of the line height when items are justified to start in column,
+// and glyphs of text are drawn below the last line, that are covered by the next item unless it is at the end of line.
+fn drawn_box_size(elm: &Element, window: &WindowStyle, is_end: bool) -> (i32, i32) {
+    let (width, height) = elm.margin_box_size();
+    let text = match elm {
+        Element::Text(Some(text)) => text,
+        _ => return (width, height),
+    };
+    let justify_content = if window.flex_direction.is_reverse() {
+        unreversed(window).justify_content
+    } else {
+        window.justify_content
+    };
+    let line_space = match justify_content {
+        JustifyContent::Start if window.flex_direction.is_column() => {
+            text.metrics.max_line_height as i32 / 2
+        }
+        _ => 0,
+    };
+    let drawn = (text.drawn_rect().height - text.content_rect().height) as i32;
+    if window.flex_direction.is_column() && !is_end {
+        (width, height + line_space)
+    } else {
+        (width, height + line_space.max(drawn))
+    }
+}
+
+// Return `window` that has the size resolved by content and window padding when it is not specified.
+// Text is sized by the space that it takes when painted, so that its glyphs are not clipped.
+pub(crate) fn sized_window(tree: &Tree, window: &WindowStyle) -> WindowStyle {
+    let (content_width, content_height) = lines_size(tree, window, |elm, is_end| {
+        drawn_box_size(elm, window, is_end)
+    });
+    let (padding_width, padding_height) = window.padding_size();
+    window.fit_content(
+        content_width + padding_width,
+        content_height + padding_height,
+    )
+}
 
 impl OGImageWriter {
     pub(crate) fn process_container(
@@ -10,21 +187,182 @@ impl OGImageWriter {
         writer: &mut OGImageWriter,
         style: Style,
     ) -> Result<(), Error> {
-        let mut image = match writer.context.image.take() {
-            Some(image) => image,
-            None => return Err(Error::NotFoundContainerImage),
-        };
+        let tree = mem::take(&mut writer.tree);
+        let content = mem::take(&mut writer.content);
+
+        let window = sized_window(&tree, &writer.window);
 
-        // TODO: support border for image
-        round(&mut image, &mut style.border_radius.clone());
+        let background = match writer.context.image.take() {
+            Some(image)
+                if image.dimensions() == (window.resolved_width(), window.resolved_height()) =>
+            {
+                Some(image)
+            }
+            _ => window.background_color.map(|color| {
+                RgbaImage::from_pixel(
+                    window.resolved_width(),
+                    window.resolved_height(),
+                    color.as_image_rgba(),
+                )
+            }),
+        };
 
-        let img = Element::Img(Some(Img::new(
-            image,
-            Rect::new(0, 0, writer.window.width, writer.window.height),
+        // Children are laid out in the content box of the container.
+        let window = WindowStyle {
+            width: Some(style.content_width(window.resolved_width())),
+            height: Some(style.content_height(window.resolved_height())),
+            ..window
+        };
+        let (inset_width, inset_height) = style.inset_size();
+        let (width, height) = (
+            window.resolved_width() + inset_width,
+            window.resolved_height() + inset_height,
+        );
+        let container = Element::Container(Some(Container {
+            rect: Rect::new(0, 0, width, height),
             style,
-        )));
+            window,
+            content,
+            tree,
+            background,
+        }));
 
-        self.process_img(img, writer.window.width, writer.window.height);
+        self.process_box(container);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::img::ImageInputFormat;
+    use crate::layout_tree::test_utils::{ROBOTO, THUMBNAIL};
+    use crate::layout_tree::{LayoutKind, LayoutRect};
+    use crate::style::{AlignItems, FlexDirection, FlexWrap, JustifyContent, Margin, Padding};
+
+    #[test]
+    fn test_layout_container() {
+        let mut container = OGImageWriter::new(WindowStyle {
+            flex_direction: FlexDirection::Row,
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        for _ in 0..2 {
+            container
+                .set_img_with_data(
+                    THUMBNAIL,
+                    100,
+                    100,
+                    ImageInputFormat::Png,
+                    Style {
+                        margin: Margin(0, 10, 0, 10),
+                        ..Style::default()
+                    },
+                )
+                .unwrap();
+        }
+
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer
+            .set_container(&mut container, Style::default())
+            .unwrap();
+        let layout = writer.layout();
+
+        let node = &layout.children[0];
+        assert_eq!(node.kind, LayoutKind::Container);
+        // Container without size is sized by its content.
+        assert_eq!(
+            node.rect,
+            LayoutRect {
+                x: 392,
+                y: 206,
+                width: 240,
+                height: 100
+            }
+        );
+        assert_eq!(node.children.len(), 2);
+        assert_eq!(node.children[0].rect.x, node.rect.x + 10);
+        assert_eq!(node.children[1].rect.x, node.rect.x + 130);
+        assert_eq!(node.children[1].rect.y, node.rect.y);
+        writer.paint().unwrap();
+    }
+
+    #[test]
+    fn test_layout_auto_size() {
+        let layout = |height, max_width, min_height| {
+            let mut writer = OGImageWriter::new(WindowStyle {
+                height,
+                max_width,
+                min_height,
+                padding: Padding(10, 20, 10, 20),
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            writer
+                .set_text(
+                    "Hello World",
+                    Style {
+                        font_size: 50.,
+                        ..Style::default()
+                    },
+                    Some(ROBOTO.to_vec()),
+                )
+                .unwrap();
+            writer
+                .set_img_with_data(THUMBNAIL, 100, 100, ImageInputFormat::Png, Style::default())
+                .unwrap();
+            writer.layout()
+        };
+
+        // Window is sized by content and padding.
+        let fit = layout(None, None, None);
+        let text = &fit.children[0];
+        assert_eq!(text.fragments.len(), 1);
+        assert_eq!(fit.width, text.margin_box.width + 40);
+        // Image that follows text by half of the line height is not clipped.
+        let img = &fit.children[1].rect;
+        assert_eq!(fit.height as i32, img.y + 100 + 10);
+
+        // Text is wrapped in the max width, and the window is extended to the min height.
+        let bounded = layout(None, Some(200), Some(400));
+        assert_eq!(bounded.children[0].fragments.len(), 2);
+        assert!(bounded.width <= 200);
+        assert_eq!(bounded.height, 400);
+
+        // Size of `0` is kept even if content overflows it.
+        let zero = layout(Some(0), None, None);
+        assert_eq!((zero.width, zero.height), (fit.width, 0));
+
+        // Wrapped lines are summed in cross axis, and the window fits the longest line.
+        let mut writer = OGImageWriter::new(WindowStyle {
+            max_width: Some(250),
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        for _ in 0..5 {
+            writer
+                .set_img_with_data(THUMBNAIL, 100, 100, ImageInputFormat::Png, Style::default())
+                .unwrap();
+        }
+        let wrap = writer.layout();
+        assert_eq!((wrap.width, wrap.height), (200, 300));
+        let positions: Vec<(i32, i32)> = wrap
+            .children
+            .iter()
+            .map(|node| (node.rect.x, node.rect.y))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![(0, 0), (100, 0), (0, 100), (100, 100), (0, 200)]
+        );
+    }
+}
@@ -1,28 +1,27 @@
 use image::ImageError;
 
-use crate::element::{Element, Img, Rect};
-use crate::img::{open_and_resize, open_and_resize_with_data, round, ImageInfo, ImageInputFormat};
-use crate::style::{FlexDirection, Margin, Style};
+use crate::element::{Element, Img};
+use crate::img::{open_and_resize, open_and_resize_with_data, ImageInfo, ImageInputFormat};
+use crate::style::Style;
 use crate::writer::OGImageWriter;
 use crate::Error;
 use std::str;
 
 impl OGImageWriter {
-    pub(super) fn process_img(&mut self, img: Element, width: u32, height: u32) {
-        let Margin(margin_top, margin_right, margin_bottom, margin_left) = img.margin();
-
-        if !img.is_absolute() {
-            match self.window.flex_direction {
-                FlexDirection::Column => {
-                    self.content.height += (height as i32 + margin_top + margin_bottom) as u32;
-                }
-                FlexDirection::Row => {
-                    self.content.width += (width as i32 + margin_left + margin_right) as u32;
-                }
+    // Push element to tree, and add its margin box and gap before it to the content size.
+    pub(super) fn process_box(&mut self, elm: Element) {
+        if !elm.is_absolute() {
+            let has_item = self.tree.0.iter().any(|elm| !elm.is_absolute());
+            let gap = if has_item { self.window.main_gap() } else { 0 };
+            let (width, height) = elm.margin_box_size();
+            if self.window.flex_direction.is_column() {
+                self.content.height += gap as i32 + height;
+            } else {
+                self.content.width += gap as i32 + width;
             }
         }
 
-        self.tree.0.push(img);
+        self.tree.0.push(elm);
     }
 
     pub(crate) fn process_img_with_src(
@@ -32,18 +31,11 @@ impl OGImageWriter {
         height: u32,
         style: Style,
     ) -> Result<(), Error> {
-        let ImageInfo(mut buf, size) = open_and_resize(src, width, height)?;
-
-        // TODO: support border for image
-        round(&mut buf, &mut style.border_radius.clone());
+        let ImageInfo(buf, size) = open_and_resize(src, width, height, style.object_fit)?;
 
-        let img = Element::Img(Some(Img::new(
-            buf,
-            Rect::new(0, 0, size.width, size.height),
-            style,
-        )));
+        let img = Element::Img(Some(Img::new(buf, size.width, size.height, style)));
 
-        self.process_img(img, size.width, size.height);
+        self.process_box(img);
 
         Ok(())
     }
@@ -56,19 +48,47 @@ impl OGImageWriter {
         format: ImageInputFormat,
         style: Style,
     ) -> Result<(), ImageError> {
-        let ImageInfo(mut buf, size) = open_and_resize_with_data(data, width, height, format)?;
-
-        // TODO: support border for image
-        round(&mut buf, &mut style.border_radius.clone());
+        let ImageInfo(buf, size) =
+            open_and_resize_with_data(data, width, height, format, style.object_fit)?;
 
-        let img = Element::Img(Some(Img::new(
-            buf,
-            Rect::new(0, 0, size.width, size.height),
-            style,
-        )));
+        let img = Element::Img(Some(Img::new(buf, size.width, size.height, style)));
 
-        self.process_img(img, size.width, size.height);
+        self.process_box(img);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::img::ImageInputFormat;
+    use crate::layout_tree::test_utils::THUMBNAIL;
+    use crate::style::{ObjectFit, Style, WindowStyle};
+    use crate::writer::OGImageWriter;
+
+    #[test]
+    fn test_layout_object_fit() {
+        let mut writer = OGImageWriter::new(WindowStyle::default()).unwrap();
+        for object_fit in [None, Some(ObjectFit::Cover)] {
+            writer
+                .set_img_with_data(
+                    THUMBNAIL,
+                    200,
+                    100,
+                    ImageInputFormat::Png,
+                    Style {
+                        object_fit,
+                        ..Style::default()
+                    },
+                )
+                .unwrap();
+        }
+        let layout = writer.layout();
+
+        // Box is shrunk to the image by default.
+        let thumbnail = &layout.children[0].rect;
+        assert_eq!((thumbnail.width, thumbnail.height), (100, 100));
+        let cover = &layout.children[1].rect;
+        assert_eq!((cover.width, cover.height), (200, 100));
+    }
+}
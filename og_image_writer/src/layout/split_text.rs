@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 use std::ops::Range;
+use std::sync::Arc;
 
 use crate::char::RenderingCharIndices;
 use crate::font::match_font_family;
@@ -9,11 +10,11 @@ use crate::glyph::Glyph;
 use crate::style::Style;
 use crate::Error;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct SplitText {
     pub(crate) text: String,
     pub(crate) style: Option<Style>,
-    pub(crate) font: Option<Box<dyn Font>>,
+    pub(crate) font: Option<Arc<dyn Font>>,
     // Fast path for glyphs.
     pub(crate) range: Range<usize>,
     pub(crate) glyphs: Vec<Glyph>,
@@ -26,6 +27,7 @@ impl SplitText {
     pub(super) fn set_glyphs(
         &mut self,
         parent_font: &Option<impl Font>,
+        parent_style: &Style,
         current_range_start: &mut usize,
         font_context: &FontContext,
     ) -> Result<(), Error> {
@@ -54,14 +56,23 @@ impl SplitText {
                 None => false,
             };
 
-            if has_child_font {
-                font_index_store = Some(FontIndexStore::Child(FontIndex(child_font_index)));
+            // Font is selected in the following order.
+            // child font -> child font family -> parent font -> parent font family -> global fallback font
+            font_index_store = if has_child_font {
+                Some(FontIndexStore::Child(FontIndex(child_font_index)))
+            } else if let Some(idx) = self
+                .style
+                .as_ref()
+                .and_then(|style| font_context.select_registered_family(style, ch))
+            {
+                Some(FontIndexStore::Global(idx))
             } else if has_parent_font {
-                font_index_store = Some(FontIndexStore::Parent(FontIndex(parent_font_index)));
+                Some(FontIndexStore::Parent(FontIndex(parent_font_index)))
             } else {
-                font_index_store =
-                    Some(FontIndexStore::Global(font_context.select_font_family(ch)?));
-            }
+                Some(FontIndexStore::Global(
+                    font_context.select_font_family_with(parent_style, ch)?,
+                ))
+            };
 
             let is_equal_font_index_store = match (&font_index_store, &prev_font_index_store) {
                 (Some(current), Some(prev)) => current == prev,
@@ -88,7 +99,7 @@ impl SplitText {
         let font_index_store = match prev_font_index_store.take() {
             Some(store) => store,
             None => {
-                self.glyphs.append(&mut glyphs);
+                self.glyphs = glyphs;
                 *current_range_start = current_range_end;
                 return Ok(());
             }
@@ -100,7 +111,8 @@ impl SplitText {
         ));
         *current_range_start = current_range_end;
 
-        self.glyphs.append(&mut glyphs);
+        // Glyphs are replaced so that text can be laid out again.
+        self.glyphs = glyphs;
 
         Ok(())
     }
@@ -120,6 +132,8 @@ mod test {
     };
 
     use super::SplitText;
+    use crate::style::Style;
+    use std::sync::Arc;
 
     #[test]
     fn test_set_glyphs() {
@@ -132,19 +146,24 @@ mod test {
         let mut split_text = SplitText {
             text: text.to_string(),
             style: None,
-            font: Some(Box::new(child_font)),
+            font: Some(Arc::new(child_font)),
             range: 0..text.len(),
             glyphs: vec![],
         };
 
         let mut current_range_start = 5;
 
-        let mut font_context = FontContext::new();
+        let font_context = FontContext::new();
         font_context.push_font(Box::new(global_normal_font));
         font_context.push_font(Box::new(global_mark_font));
 
         split_text
-            .set_glyphs(&Some(parent_font), &mut current_range_start, &font_context)
+            .set_glyphs(
+                &Some(parent_font),
+                &Style::default(),
+                &mut current_range_start,
+                &font_context,
+            )
             .unwrap();
 
         assert!(!split_text.glyphs.is_empty());
@@ -177,4 +196,69 @@ mod test {
             FontIndexStore::Global(FontIndex(1))
         );
     }
+
+    #[test]
+    fn test_set_glyphs_with_font_family() {
+        let text = "childFAMILYparent!";
+
+        let child_font = FontMock::new(Some("child"));
+        let parent_font = FontMock::new(Some("parent"));
+        let family_font = FontMock::new(Some("FAMILY"));
+        let parent_family_font = FontMock::new(Some("!"));
+        let mut split_text = SplitText {
+            text: text.to_string(),
+            style: Some(Style {
+                font_family: vec!["Unknown".to_string(), "Family".to_string()],
+                ..Style::default()
+            }),
+            font: Some(Arc::new(child_font)),
+            range: 0..text.len(),
+            glyphs: vec![],
+        };
+
+        let mut current_range_start = 0;
+
+        let font_context = FontContext::new();
+        font_context.register_font("family", Box::new(family_font));
+        font_context.register_font("Parent Family", Box::new(parent_family_font));
+
+        split_text
+            .set_glyphs(
+                &Some(parent_font),
+                &Style {
+                    font_family: vec!["Parent Family".to_string()],
+                    ..Style::default()
+                },
+                &mut current_range_start,
+                &font_context,
+            )
+            .unwrap();
+
+        let mut glyphs_iter = split_text.glyphs.iter();
+
+        let child = glyphs_iter.next().unwrap();
+        assert_eq!(child.range, 0..5);
+        assert_eq!(child.font_index_store, FontIndexStore::Child(FontIndex(0)));
+
+        let family = glyphs_iter.next().unwrap();
+        assert_eq!(family.range, 5..11);
+        assert_eq!(
+            family.font_index_store,
+            FontIndexStore::Global(FontIndex(0))
+        );
+
+        let parent = glyphs_iter.next().unwrap();
+        assert_eq!(parent.range, 11..17);
+        assert_eq!(
+            parent.font_index_store,
+            FontIndexStore::Parent(FontIndex(0))
+        );
+
+        let parent_family = glyphs_iter.next().unwrap();
+        assert_eq!(parent_family.range, 17..18);
+        assert_eq!(
+            parent_family.font_index_store,
+            FontIndexStore::Global(FontIndex(1))
+        );
+    }
 }
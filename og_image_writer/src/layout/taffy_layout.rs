@@ -0,0 +1,702 @@
+use super::container::wrap_lines;
+use super::text::layout_text;
+use super::{shift_line_height, unreversed};
+use crate::element::{Element, Rect, Text};
+use crate::style::{
+    AlignContent, AlignItems, FlexDirection, FlexWrap, JustifyContent, Margin, Padding, Position,
+    Style, WindowStyle,
+};
+use crate::writer::Tree;
+use taffy::{
+    AvailableSpace, Dimension, Layout, LengthPercentage, LengthPercentageAuto, NodeId, Size,
+    TaffyResult, TaffyTree,
+};
+
+// Flexbox layout of elements in window that is solved by taffy.
+// Nested containers are laid out in the same taffy tree, and text is measured by breaking lines in available width.
+pub(crate) struct TaffyLayout<'a> {
+    window: &'a WindowStyle,
+}
+
+impl<'a> TaffyLayout<'a> {
+    pub(crate) fn new(window: &'a WindowStyle) -> Self {
+        TaffyLayout { window }
+    }
+
+    pub(crate) fn process_tree(&self, tree: &mut Tree) {
+        // Taffy fails only for node that is not in the taffy tree.
+        if let Ok(layouts) = self.compute(tree) {
+            let lines = wrap_lines(tree, self.window);
+            place_tree(tree, &mut layouts.into_iter(), 0, 0);
+            let content = Rect::new(
+                0,
+                0,
+                self.window.resolved_width(),
+                self.window.resolved_height(),
+            );
+            align_builtin(
+                tree,
+                self.window,
+                &content.shrink(&self.window.padding),
+                &lines,
+            );
+        }
+    }
+
+    // Return layout of each element in pre-order.
+    fn compute(&self, tree: &Tree) -> TaffyResult<Vec<Layout>> {
+        let mut taffy: TaffyTree<&Text> = TaffyTree::new();
+        let mut nodes = vec![];
+        let offsets = (self.window.padding, Padding::default());
+        let children = build_tree(&mut taffy, tree, self.window, offsets, &mut nodes)?;
+
+        let mut style = taffy::Style {
+            size: Size {
+                width: auto_or_length(self.window.width),
+                height: auto_or_length(self.window.height),
+            },
+            ..taffy::Style::default()
+        };
+        set_flex_container(&mut style, self.window, &self.window.padding);
+        let root = taffy.new_with_children(style, &children)?;
+
+        let available_space = Size {
+            width: available_space(self.window.width),
+            height: available_space(self.window.height),
+        };
+        taffy.compute_layout_with_measure(
+            root,
+            available_space,
+            |known_dimensions, available_space, _, text, _| match text {
+                Some(text) => measure_text(text, known_dimensions, available_space),
+                None => Size::ZERO,
+            },
+        )?;
+
+        nodes
+            .into_iter()
+            .map(|node| taffy.layout(node).copied())
+            .collect()
+    }
+}
+
+// Add elements in `tree` that are laid out in `window` to `taffy`, and push node of each element to `nodes` in pre-order.
+// `offsets` are space between the padding box of the window and the area that absolute element is placed in,
+// for element without and with `ignore_window_padding`.
+fn build_tree<'t>(
+    taffy: &mut TaffyTree<&'t Text>,
+    tree: &'t Tree,
+    window: &WindowStyle,
+    offsets: (Padding, Padding),
+    nodes: &mut Vec<NodeId>,
+) -> TaffyResult<Vec<NodeId>> {
+    let is_column = window.flex_direction.is_column();
+    let mut children = vec![];
+    for elm in &tree.0 {
+        let style = match elm.style() {
+            Some(style) => style,
+            None => continue,
+        };
+        let offset = if style.ignore_window_padding {
+            &offsets.1
+        } else {
+            &offsets.0
+        };
+        let node = match elm {
+            Element::Img(Some(img)) => {
+                taffy.new_leaf(item_style(style, Some(&img.rect), offset, is_column))?
+            }
+            Element::Text(Some(text)) => {
+                taffy.new_leaf_with_context(item_style(style, None, offset, is_column), text)?
+            }
+            Element::Container(Some(container)) => {
+                let mut node_style = item_style(style, Some(&container.rect), offset, is_column);
+                set_flex_container(
+                    &mut node_style,
+                    &container.window,
+                    &container_padding(style, &container.window),
+                );
+                taffy.new_leaf(node_style)?
+            }
+            _ => continue,
+        };
+        nodes.push(node);
+
+        if let Element::Container(Some(container)) = elm {
+            let offsets = (container_padding(style, &container.window), style.padding);
+            let children = build_tree(taffy, &container.tree, &container.window, offsets, nodes)?;
+            taffy.set_children(node, &children)?;
+        }
+        children.push(node);
+    }
+    Ok(children)
+}
+
+// Move elements in `tree` to computed `layouts`. `x` and `y` are position of the border box of the parent.
+fn place_tree(tree: &mut Tree, layouts: &mut impl Iterator<Item = Layout>, x: i32, y: i32) {
+    for elm in &mut tree.0 {
+        if elm.style().is_none() {
+            continue;
+        }
+        let layout = match layouts.next() {
+            Some(layout) => layout,
+            None => return,
+        };
+        let rect = Rect::new(
+            x + layout.location.x.round() as i32,
+            y + layout.location.y.round() as i32,
+            layout.size.width.round() as u32,
+            layout.size.height.round() as u32,
+        );
+        match elm {
+            Element::Img(Some(img)) => img.rect = rect,
+            Element::Text(Some(text)) => place_text(text, &rect),
+            Element::Container(Some(container)) => {
+                let (inset_width, inset_height) = container.style.inset_size();
+                container.window.width = Some(rect.width.saturating_sub(inset_width));
+                container.window.height = Some(rect.height.saturating_sub(inset_height));
+                let lines = wrap_lines(&container.tree, &container.window);
+                place_tree(&mut container.tree, layouts, rect.x, rect.y);
+                let content = rect
+                    .shrink(&container.style.inset())
+                    .shrink(&container.window.padding);
+                align_builtin(&mut container.tree, &container.window, &content, &lines);
+                container.rect = rect;
+            }
+            _ => {}
+        }
+    }
+}
+
+// Move lines of text into the content box of border box `rect`.
+// Text is broken into lines again only when it is resized, like the built-in layout.
+fn place_text(text: &mut Text, rect: &Rect) {
+    let content = rect.shrink(&text.style.inset());
+    if content.width != text.metrics.max_line_width as u32 {
+        text.set_width(content.width);
+    }
+    text.metrics.total_height = content.height;
+    for i in 0..text.fragments.len() {
+        let offset = text.line_offset(&text.fragments[i]);
+        let fragment = &mut text.fragments[i];
+        fragment.rect.x = content.x + offset;
+        fragment.rect.y += content.y;
+    }
+}
+
+// Return size of the content box of text in available width.
+// Lines that are broken when text is set are used if they fit, and text is broken again if it does not fit.
+fn measure_text(
+    text: &Text,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+) -> Size<f32> {
+    // Known dimensions are size of the border box.
+    let (inset_width, inset_height) = text.style.inset_size();
+    let known_width = known_dimensions
+        .width
+        .map(|width| (width - inset_width as f32).max(0.));
+    let known_height = known_dimensions
+        .height
+        .map(|height| (height - inset_height as f32).max(0.));
+    let width = match (known_width, available_space.width) {
+        (Some(width), _) => width,
+        (None, AvailableSpace::Definite(width)) => width,
+        (None, AvailableSpace::MinContent) => 0.,
+        (None, AvailableSpace::MaxContent) => f32::INFINITY,
+    };
+
+    let (max_line_width, total_height) = if width >= text.metrics.max_line_width {
+        (text.metrics.max_line_width, text.metrics.total_height)
+    } else {
+        let mut textarea = text.source.clone();
+        let layout = layout_text(
+            &mut textarea,
+            &text.style,
+            &text.font,
+            width,
+            &text.font_context,
+        );
+        match layout {
+            // Size is truncated in the same way as `Text::set_width`.
+            Ok(layout) => (
+                layout.max_line_width as u32 as f32,
+                layout.total_height as u32,
+            ),
+            // Fonts are already resolved when text is set, so the current lines are used if it fails.
+            Err(_) => (text.metrics.max_line_width, text.metrics.total_height),
+        }
+    };
+    Size {
+        width: known_width.unwrap_or(max_line_width),
+        height: known_height.unwrap_or(total_height as f32),
+    }
+}
+
+// Move items in `tree` laid out in `window` to the position of the built-in layout. `content` is the content box of `window`.
+// The built-in layout centers items without their spacing in cross axis,
+// and shifts text by half of the line height because glyphs are drawn with the space of line height.
+// `lines` are index of items in each line, that are broken before items are placed like the built-in layout.
+fn align_builtin(tree: &mut Tree, window: &WindowStyle, content: &Rect, lines: &[Vec<usize>]) {
+    let is_column = window.flex_direction.is_column();
+    // Reversed line is aligned as the mirror of the line.
+    let justify_content = if window.flex_direction.is_reverse() {
+        unreversed(window).justify_content
+    } else {
+        window.justify_content
+    };
+
+    for elm in &mut tree.0 {
+        if !elm.is_absolute() {
+            continue;
+        }
+        if let Element::Text(Some(text)) = elm {
+            if text.style.top.is_none() && text.style.bottom.is_some() {
+                let system_line_height = system_line_height(text);
+                translate_lines(text, -system_line_height);
+            }
+        }
+    }
+
+    for indexes in lines {
+        let mut line: Vec<&mut Element> = tree
+            .0
+            .iter_mut()
+            .enumerate()
+            .filter(|(i, elm)| indexes.contains(i) && elm.style().is_some())
+            .map(|(_, elm)| elm)
+            .collect();
+        // Items are aligned from the start of main axis.
+        if window.flex_direction.is_reverse() {
+            line.reverse();
+        }
+
+        // Wrapped line has the size of the largest item in cross axis.
+        let (line_start, line_size) = match window.flex_wrap {
+            FlexWrap::NoWrap if is_column => (content.x, content.width as i32),
+            FlexWrap::NoWrap => (content.y, content.height as i32),
+            FlexWrap::Wrap | FlexWrap::WrapReverse => cross_extent(&line, is_column),
+        };
+        for elm in line.iter_mut() {
+            let align_items = match elm.style().and_then(|style| style.align_self) {
+                Some(align_self) => align_self,
+                None => window.align_items,
+            };
+            if matches!(align_items, AlignItems::Center) {
+                center_item(elm, is_column, line_start, line_size);
+            }
+        }
+
+        if is_column {
+            shift_column(&mut line, &justify_content, content.y);
+        } else {
+            shift_row(&mut line, window, line_start);
+        }
+    }
+}
+
+// Center item in cross axis of the line at `line_start` in the same way as the built-in layout,
+// that centers the box without spacing, and then adds spacing of the start side and subtracts spacing of the end side.
+fn center_item(elm: &mut Element, is_column: bool, line_start: i32, line_size: i32) {
+    let (rect, spacing) = match elm {
+        Element::Img(Some(img)) => (img.rect.clone(), img.style.margin),
+        Element::Text(Some(text)) => (text.content_rect(), text.style.spacing()),
+        Element::Container(Some(container)) => (container.rect.clone(), container.style.margin),
+        _ => return,
+    };
+    let Margin(top, right, bottom, left) = spacing;
+    if is_column {
+        let x = line_start + line_size / 2 - rect.width as i32 / 2 + left - right;
+        elm.translate(x - rect.x, 0);
+    } else {
+        let y = line_start + line_size / 2 - rect.height as i32 / 2 + top - bottom;
+        elm.translate(0, y - rect.y);
+    }
+}
+
+// Text is followed by space of half of the line height when items are justified to start,
+// and is moved up by half of the line height otherwise.
+fn shift_column(line: &mut [&mut Element], justify_content: &JustifyContent, top: i32) {
+    let mut offset = 0;
+    for elm in line {
+        elm.translate(0, offset);
+        if let Element::Text(Some(text)) = elm {
+            let mut system_line_height = system_line_height(text);
+            match justify_content {
+                JustifyContent::Start => offset += system_line_height,
+                JustifyContent::End => translate_lines(text, -system_line_height),
+                _ => shift_lines(text, &mut system_line_height, top),
+            }
+        }
+    }
+}
+
+// Text is moved up by half of the line height unless it is aligned to start.
+fn shift_row(line: &mut [&mut Element], window: &WindowStyle, top: i32) {
+    for elm in line {
+        if let Element::Text(Some(text)) = elm {
+            let mut system_line_height = system_line_height(text);
+            match text.style.align_self.unwrap_or(window.align_items) {
+                AlignItems::Start => {}
+                AlignItems::Center => shift_lines(text, &mut system_line_height, top),
+                AlignItems::End => translate_lines(text, -system_line_height),
+            }
+        }
+    }
+}
+
+// Because ab_glyph draw text that include line_height.
+fn system_line_height(text: &Text) -> i32 {
+    text.metrics.max_line_height as i32 / 2
+}
+
+fn translate_lines(text: &mut Text, dy: i32) {
+    for fragment in &mut text.fragments {
+        fragment.rect.y += dy;
+    }
+}
+
+// Move lines up by `system_line_height`, but not across `top`.
+fn shift_lines(text: &mut Text, system_line_height: &mut i32, top: i32) {
+    for fragment in &mut text.fragments {
+        fragment.rect.y -= top;
+        shift_line_height(&mut fragment.rect, system_line_height);
+        fragment.rect.y += top;
+    }
+}
+
+// Return position of the margin box of placed element.
+fn margin_box_start(elm: &Element) -> (i32, i32) {
+    let (x, y) = match elm {
+        Element::Img(Some(img)) => (img.rect.x, img.rect.y),
+        Element::Text(Some(text)) => {
+            let content = text.content_rect();
+            let Padding(top, _, _, left) = text.style.inset();
+            (content.x - left as i32, content.y - top as i32)
+        }
+        Element::Container(Some(container)) => (container.rect.x, container.rect.y),
+        _ => (0, 0),
+    };
+    let Margin(top, _, _, left) = elm.margin();
+    (x - left, y - top)
+}
+
+// Return start and size of margin boxes of `line` in cross axis.
+fn cross_extent(line: &[&mut Element], is_column: bool) -> (i32, i32) {
+    let (mut start, mut end) = (i32::MAX, i32::MIN);
+    for elm in line {
+        let (x, y) = margin_box_start(elm);
+        let (width, height) = elm.margin_box_size();
+        let (item_start, item_size) = if is_column { (x, width) } else { (y, height) };
+        start = start.min(item_start);
+        end = end.max(item_start + item_size);
+    }
+    (start, (end - start).max(0))
+}
+
+// Style of element as flex item in window of `is_column` direction.
+// Size of image and container is fixed to `border_box`, because it is resolved when they are set.
+fn item_style(
+    style: &Style,
+    border_box: Option<&Rect>,
+    offset: &Padding,
+    is_column: bool,
+) -> taffy::Style {
+    // Sizes are converted to the border box with `box_sizing`.
+    let (inset_width, inset_height) = style.inset_size();
+    let width = |size: Option<u32>| match size {
+        Some(size) => Dimension::length(style.border_box(size, inset_width) as f32),
+        None => Dimension::auto(),
+    };
+    let height = |size: Option<u32>| match size {
+        Some(size) => Dimension::length(style.border_box(size, inset_height) as f32),
+        None => Dimension::auto(),
+    };
+    let (size, min_size, max_size) = match border_box {
+        Some(rect) => (
+            Size {
+                width: Dimension::length(rect.width as f32),
+                height: Dimension::length(rect.height as f32),
+            },
+            Size::auto(),
+            Size::auto(),
+        ),
+        None => (
+            Size {
+                width: width(style.width),
+                height: height(style.height),
+            },
+            Size {
+                width: width(style.min_width),
+                height: height(style.min_height),
+            },
+            Size {
+                width: width(style.max_width),
+                height: height(style.max_height),
+            },
+        ),
+    };
+    let flex_basis = match (style.flex_basis, is_column) {
+        (Some(basis), true) => height(Some(basis)),
+        (Some(basis), false) => width(Some(basis)),
+        (None, _) => Dimension::auto(),
+    };
+
+    taffy::Style {
+        position: match style.position {
+            Position::Static => taffy::Position::Relative,
+            Position::Absolute => taffy::Position::Absolute,
+        },
+        inset: match style.position {
+            Position::Static => taffy::Rect::auto(),
+            Position::Absolute => inset_rect(style, offset),
+        },
+        box_sizing: taffy::BoxSizing::BorderBox,
+        size,
+        min_size,
+        max_size,
+        margin: taffy::Rect {
+            top: LengthPercentageAuto::length(style.margin.0 as f32),
+            right: LengthPercentageAuto::length(style.margin.1 as f32),
+            bottom: LengthPercentageAuto::length(style.margin.2 as f32),
+            left: LengthPercentageAuto::length(style.margin.3 as f32),
+        },
+        padding: padding_rect(&style.padding),
+        border: padding_rect(&Padding(
+            style.border_width,
+            style.border_width,
+            style.border_width,
+            style.border_width,
+        )),
+        align_self: style.align_self.map(align_items),
+        flex_grow: style.flex_grow,
+        flex_shrink: style.flex_shrink,
+        flex_basis,
+        ..taffy::Style::default()
+    }
+}
+
+// Lay out children of node with `style` as `window`, inside `padding`.
+fn set_flex_container(style: &mut taffy::Style, window: &WindowStyle, padding: &Padding) {
+    style.display = taffy::Display::Flex;
+    style.padding = padding_rect(padding);
+    style.flex_direction = match window.flex_direction {
+        FlexDirection::Column => taffy::FlexDirection::Column,
+        FlexDirection::Row => taffy::FlexDirection::Row,
+        FlexDirection::ColumnReverse => taffy::FlexDirection::ColumnReverse,
+        FlexDirection::RowReverse => taffy::FlexDirection::RowReverse,
+    };
+    style.flex_wrap = match window.flex_wrap {
+        FlexWrap::NoWrap => taffy::FlexWrap::NoWrap,
+        FlexWrap::Wrap => taffy::FlexWrap::Wrap,
+        FlexWrap::WrapReverse => taffy::FlexWrap::WrapReverse,
+    };
+    style.justify_content = Some(match window.justify_content {
+        JustifyContent::Start => taffy::JustifyContent::FlexStart,
+        JustifyContent::Center => taffy::JustifyContent::Center,
+        JustifyContent::End => taffy::JustifyContent::FlexEnd,
+        JustifyContent::SpaceBetween => taffy::JustifyContent::SpaceBetween,
+        JustifyContent::SpaceAround => taffy::JustifyContent::SpaceAround,
+        JustifyContent::SpaceEvenly => taffy::JustifyContent::SpaceEvenly,
+    });
+    style.align_items = Some(align_items(window.align_items));
+    style.align_content = Some(match window.align_content {
+        AlignContent::Start => taffy::AlignContent::FlexStart,
+        AlignContent::Center => taffy::AlignContent::Center,
+        AlignContent::End => taffy::AlignContent::FlexEnd,
+        AlignContent::SpaceBetween => taffy::AlignContent::SpaceBetween,
+        AlignContent::SpaceAround => taffy::AlignContent::SpaceAround,
+        AlignContent::Stretch => taffy::AlignContent::Stretch,
+    });
+    style.gap = Size {
+        width: LengthPercentage::length(window.column_gap.unwrap_or(window.gap) as f32),
+        height: LengthPercentage::length(window.row_gap.unwrap_or(window.gap) as f32),
+    };
+}
+
+fn align_items(align_items: AlignItems) -> taffy::AlignItems {
+    match align_items {
+        AlignItems::Start => taffy::AlignItems::Start,
+        AlignItems::Center => taffy::AlignItems::Center,
+        AlignItems::End => taffy::AlignItems::End,
+    }
+}
+
+// Inset of absolute element from the padding box of the window.
+// Element is placed at the start when neither of the sides is specified.
+fn inset_rect(style: &Style, offset: &Padding) -> taffy::Rect<LengthPercentageAuto> {
+    let Padding(top, right, bottom, left) = *offset;
+    let inset =
+        |value: i32, offset: u32| LengthPercentageAuto::length((value + offset as i32) as f32);
+    let (left, right) = match (style.left, style.right) {
+        (Some(value), _) => (inset(value, left), LengthPercentageAuto::auto()),
+        (None, Some(value)) => (LengthPercentageAuto::auto(), inset(value, right)),
+        (None, None) => (inset(0, left), LengthPercentageAuto::auto()),
+    };
+    let (top, bottom) = match (style.top, style.bottom) {
+        (Some(value), _) => (inset(value, top), LengthPercentageAuto::auto()),
+        (None, Some(value)) => (LengthPercentageAuto::auto(), inset(value, bottom)),
+        (None, None) => (inset(0, top), LengthPercentageAuto::auto()),
+    };
+    taffy::Rect {
+        left,
+        right,
+        top,
+        bottom,
+    }
+}
+
+// Padding of container node, that is padding of its style and window.
+fn container_padding(style: &Style, window: &WindowStyle) -> Padding {
+    let Padding(top, right, bottom, left) = style.padding;
+    let Padding(window_top, window_right, window_bottom, window_left) = window.padding;
+    Padding(
+        top + window_top,
+        right + window_right,
+        bottom + window_bottom,
+        left + window_left,
+    )
+}
+
+fn padding_rect(padding: &Padding) -> taffy::Rect<LengthPercentage> {
+    let Padding(top, right, bottom, left) = *padding;
+    taffy::Rect {
+        top: LengthPercentage::length(top as f32),
+        right: LengthPercentage::length(right as f32),
+        bottom: LengthPercentage::length(bottom as f32),
+        left: LengthPercentage::length(left as f32),
+    }
+}
+
+// Size of `None` means that window is sized by content.
+fn auto_or_length(size: Option<u32>) -> Dimension {
+    match size {
+        Some(size) => Dimension::length(size as f32),
+        None => Dimension::auto(),
+    }
+}
+
+fn available_space(size: Option<u32>) -> AvailableSpace {
+    match size {
+        Some(size) => AvailableSpace::Definite(size as f32),
+        None => AvailableSpace::MaxContent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::img::ImageInputFormat;
+    use crate::layout::LayoutEngine;
+    use crate::layout_tree::test_utils::{writer_with_window, ROBOTO, THUMBNAIL};
+    use crate::style::ObjectFit;
+    use crate::writer::OGImageWriter;
+
+    #[test]
+    fn test_layout_taffy_engine() {
+        // Elements are placed at the same position as the built-in layout.
+        for flex_direction in [FlexDirection::Column, FlexDirection::Row] {
+            for justify_content in [
+                JustifyContent::Start,
+                JustifyContent::Center,
+                JustifyContent::End,
+                JustifyContent::SpaceBetween,
+            ] {
+                let layout = |layout_engine| {
+                    let mut writer = writer_with_window(WindowStyle {
+                        width: Some(1024),
+                        height: Some(512),
+                        align_items: AlignItems::Center,
+                        flex_direction,
+                        justify_content,
+                        ..WindowStyle::default()
+                    });
+                    writer.set_layout_engine(layout_engine);
+                    writer.layout()
+                };
+                let expected = layout(LayoutEngine::Builtin);
+                let actual = layout(LayoutEngine::Taffy);
+                assert_eq!(actual.children.len(), 2);
+                assert_eq!(actual.children, expected.children);
+            }
+        }
+
+        // Text follows the margin box of the image.
+        let mut taffy = writer_with_window(WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            align_items: AlignItems::Center,
+            ..WindowStyle::default()
+        });
+        taffy.set_layout_engine(LayoutEngine::Taffy);
+        let layout = taffy.layout();
+        assert_eq!(layout.children[0].rect.y, 10);
+        assert_eq!(layout.children[1].rect.y, 130);
+
+        let window = WindowStyle {
+            width: Some(1000),
+            height: Some(500),
+            flex_direction: FlexDirection::Row,
+            ..WindowStyle::default()
+        };
+        let styles = [1., 3.].map(|flex_grow| Style {
+            flex_grow,
+            ..Style::default()
+        });
+        let mut writer = OGImageWriter::new(window).unwrap();
+        writer.set_layout_engine(LayoutEngine::Taffy);
+        for style in styles {
+            writer
+                .set_img_with_data(THUMBNAIL, 100, 100, ImageInputFormat::Png, style)
+                .unwrap();
+        }
+        let layout = writer.layout();
+
+        // Free space is distributed by flex_grow, but images keep their size.
+        assert_eq!(layout.children[0].rect.x, 0);
+        assert_eq!(layout.children[1].rect.x, 300);
+    }
+    #[test]
+    fn test_layout_taffy_wrap_lines() {
+        // Item that overlaps the previous item by negative margin is kept in the same line.
+        let layout = |layout_engine| {
+            let mut writer = OGImageWriter::new(WindowStyle {
+                width: Some(1024),
+                height: Some(512),
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                align_items: AlignItems::Center,
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            writer.set_layout_engine(layout_engine);
+            writer
+                .set_img_with_data(
+                    THUMBNAIL,
+                    100,
+                    300,
+                    ImageInputFormat::Png,
+                    Style {
+                        margin: Margin(0, -150, 0, 0),
+                        object_fit: Some(ObjectFit::Fill),
+                        ..Style::default()
+                    },
+                )
+                .unwrap();
+            writer
+                .set_text(
+                    "Hello World",
+                    Style {
+                        font_size: 50.,
+                        ..Style::default()
+                    },
+                    Some(ROBOTO.to_vec()),
+                )
+                .unwrap();
+            writer.layout()
+        };
+        let expected = layout(LayoutEngine::Builtin);
+        let actual = layout(LayoutEngine::Taffy);
+        assert_eq!(actual.children, expected.children);
+    }
+}
@@ -3,218 +3,299 @@ use super::textarea::TextArea;
 use crate::char::{CharFlags, RevRenderingCharIndices};
 use crate::element::{Element, Fragment, LineMetrics, Rect, Text};
 use crate::font::{match_font_family, whitespace_width, FontArc, FontMetrics};
+use crate::font_context::FontContext;
 use crate::font_trait::Font;
 use crate::line_breaker::LineBreaker;
 use crate::renderer::FontSetting;
-use crate::style::{FlexDirection, Margin, Position, Style, TextOverflow};
+use crate::style::{Margin, Position, Style, TextOverflow};
 use crate::writer::OGImageWriter;
 use crate::Error;
 use std::cell::RefCell;
 use std::str;
 
-impl OGImageWriter {
-    pub(crate) fn process_text(
-        &mut self,
-        textarea: RefCell<TextArea>,
-        // Parent style that effect child element
-        style: Style,
-        // Parent font that effect child element
-        font: Option<FontArc>,
-    ) -> Result<(), Error> {
-        let window_width = self.window.width as f32;
+// Result of line breaking for text.
+pub(crate) struct TextLayout {
+    // Text to render. This includes ellipsis when text is overflowed.
+    pub(crate) text: String,
+    pub(crate) lines: Vec<Fragment>,
+    pub(crate) total_height: f32,
+    pub(crate) max_line_height: f32,
+    pub(crate) max_line_width: f32,
+    pub(crate) is_overflow: bool,
+}
 
-        let Margin(margin_top, margin_right, margin_bottom, margin_left) = style.margin;
+// Return width of the content box that text can be laid out in `container_width`.
+// Width of `0` means that container is sized by content, so text is not wrapped by container.
+pub(crate) fn text_area_width(style: &Style, container_width: u32) -> i32 {
+    if style.width.is_some() {
+        return style.content_width(0) as i32;
+    }
 
-        let (left, right) = if matches!(style.position, Position::Absolute) {
-            (
-                style.left.unwrap_or(0) + margin_left,
-                style.right.unwrap_or(0) + margin_right,
-            )
-        } else {
-            (margin_left, margin_right)
-        };
+    let Margin(_, margin_right, _, margin_left) = style.margin;
 
-        let text_area_width = match style.max_width {
-            Some(max_width) => max_width as i32,
-            None => window_width as i32,
-        } - left
-            - right;
+    let (left, right) = if matches!(style.position, Position::Absolute) {
+        (
+            style.left.unwrap_or(0) + margin_left,
+            style.right.unwrap_or(0) + margin_right,
+        )
+    } else {
+        (margin_left, margin_right)
+    };
 
-        let text = textarea.borrow().as_string();
+    let (inset, _) = style.inset_size();
+    let width = container_width as i32 - left - right - inset as i32;
+    match style.max_width {
+        Some(max_width) => width.min(style.content_box(max_width, inset) as i32),
+        None => width,
+    }
+}
 
-        textarea
-            .borrow_mut()
-            .set_glyphs(&font, &self.font_context)?;
+// Break text into lines and shape ellipsis when text is overflowed.
+pub(crate) fn layout_text(
+    textarea: &mut TextArea,
+    // Parent style that effect child element
+    style: &Style,
+    // Parent font that effect child element
+    font: &Option<FontArc>,
+    text_area_width: f32,
+    font_context: &FontContext,
+) -> Result<TextLayout, Error> {
+    let text = textarea.as_string();
 
-        let mut line_breaker = LineBreaker::new(&text);
-        line_breaker.break_text(
-            text_area_width as f32,
-            &style,
-            &font,
-            &textarea.borrow(),
-            &self.font_context,
-        )?;
+    textarea.set_glyphs(font, style, font_context)?;
 
-        let max_line_height = line_breaker.max_line_height;
-        let max_line_width = line_breaker.max_line_width;
-
-        let mut lines: Vec<Fragment> = vec![];
-
-        // Calculate line position
-        let mut total_height = 0.;
-        let line_height = max_line_height * style.line_height / 2. - max_line_height / 2.;
-        let lines_len = line_breaker.lines.len();
-        let mut is_overflow = false;
-        for (i, line) in line_breaker.lines.into_iter().enumerate() {
-            let is_first_line = i == 0;
-            let next_height = if is_first_line {
-                total_height + max_line_height
-            } else {
-                total_height + max_line_height + line_height
-            };
-
-            match style.max_height {
-                Some(max_height) if next_height > max_height as f32 => {
-                    is_overflow = true;
-                    break;
-                }
-                _ => {}
-            }
+    let mut line_breaker = LineBreaker::new(&text);
+    line_breaker.break_text(text_area_width, style, font, textarea, font_context)?;
+
+    let max_line_height = line_breaker.max_line_height;
+    let max_line_width = line_breaker.max_line_width;
+
+    let mut lines: Vec<Fragment> = vec![];
+
+    // Calculate line position
+    let mut total_height = 0.;
+    let line_height = max_line_height * style.line_height / 2. - max_line_height / 2.;
+    let lines_len = line_breaker.lines.len();
+    let mut is_overflow = false;
+    for (i, line) in line_breaker.lines.into_iter().enumerate() {
+        let is_first_line = i == 0;
+        let next_height = if is_first_line {
+            total_height + max_line_height
+        } else {
+            total_height + max_line_height + line_height
+        };
 
-            if lines_len == 1 {
-                total_height = next_height;
-                lines.push(Fragment::new(
-                    line.range,
-                    Rect::new(0, 0, line.width as u32, line.height as u32),
-                ));
+        match style.content_max_height() {
+            Some(max_height) if next_height > max_height as f32 => {
+                is_overflow = true;
                 break;
             }
+            _ => {}
+        }
 
-            let pos_y = total_height;
-            let pos_y = if !is_first_line {
-                pos_y + line_height
-            } else {
-                pos_y
-            };
-
+        if lines_len == 1 {
             total_height = next_height;
             lines.push(Fragment::new(
                 line.range,
-                Rect::new(0, pos_y as u32, line.width as u32, line.height as u32),
+                Rect::new(0, 0, line.width as u32, line.height as u32),
             ));
+            break;
         }
 
-        let text = if is_overflow {
-            self.set_ellipsis(
-                &text[0..lines.last().unwrap().range.end],
-                &mut lines,
-                &style,
-                &font,
-                &mut textarea.borrow_mut(),
-            )?
+        let pos_y = total_height;
+        let pos_y = if !is_first_line {
+            pos_y + line_height
         } else {
-            text.to_string()
+            pos_y
         };
 
+        total_height = next_height;
+        lines.push(Fragment::new(
+            line.range,
+            Rect::new(0, pos_y as i32, line.width as u32, line.height as u32),
+        ));
+    }
+
+    let text = match lines.last() {
+        Some(line) if is_overflow => set_ellipsis(
+            &text[0..line.range.end],
+            &mut lines,
+            style,
+            font,
+            textarea,
+            font_context,
+        )?,
+        // Even the first line does not fit in the height.
+        None if is_overflow => String::new(),
+        _ => text,
+    };
+
+    Ok(TextLayout {
+        text,
+        lines,
+        total_height,
+        max_line_height,
+        max_line_width,
+        is_overflow,
+    })
+}
+
+impl Text {
+    // Break lines again in `width`, and resize the text box to `width`.
+    // Fonts are already resolved when text is set, so line breaking is not expected to fail.
+    // The previous lines are kept if it fails.
+    pub(crate) fn set_width(&mut self, width: u32) {
+        let mut textarea = self.source.clone();
+        let layout = layout_text(
+            &mut textarea,
+            &self.style,
+            &self.font,
+            width as f32,
+            &self.font_context,
+        );
+        if let Ok(layout) = layout {
+            self.text = layout.text;
+            self.fragments = layout.lines;
+            self.metrics = LineMetrics::new(
+                layout.total_height as u32,
+                layout.max_line_height,
+                width as f32,
+            );
+            self.textarea = textarea;
+        }
+    }
+}
+
+impl OGImageWriter {
+    pub(crate) fn process_text(
+        &mut self,
+        textarea: RefCell<TextArea>,
+        // Parent style that effect child element
+        style: Style,
+        // Parent font that effect child element
+        font: Option<FontArc>,
+    ) -> Result<(), Error> {
+        let mut textarea = textarea.into_inner();
+        let source = textarea.clone();
+        let TextLayout {
+            text,
+            lines,
+            total_height,
+            max_line_height,
+            max_line_width,
+            ..
+        } = layout_text(
+            &mut textarea,
+            &style,
+            &font,
+            text_area_width(&style, self.window.text_width(&style)) as f32,
+            &self.font_context,
+        )?;
+
         let text_elm = Element::Text(Some(Text::new(
             text,
             lines,
-            LineMetrics::new(total_height as u32, max_line_height, max_line_width),
+            LineMetrics::new(
+                style.content_height(total_height as u32),
+                max_line_height,
+                style.content_width(max_line_width as u32) as f32,
+            ),
             style,
             font,
-            textarea.into_inner(),
+            textarea,
+            source,
+            self.font_context.clone(),
         )));
 
-        // TODO: refactor
-        if !text_elm.is_absolute() {
-            match self.window.flex_direction {
-                FlexDirection::Column => {
-                    self.content.height +=
-                        (total_height as i32 + margin_top + margin_bottom) as u32;
-                }
-                FlexDirection::Row => {
-                    self.content.width +=
-                        (max_line_width as i32 + margin_left + margin_right) as u32;
-                }
-            }
-        }
-
-        self.tree.0.push(text_elm);
+        self.process_box(text_elm);
 
         Ok(())
     }
+}
 
-    fn set_ellipsis(
-        &mut self,
-        text: &str,
-        fragments: &mut [Fragment],
-        style: &Style,
-        font: &Option<FontArc>,
-        textarea: &mut TextArea,
-    ) -> Result<String, Error> {
-        fn rev_char_extents<F>(
-            is_newline: bool,
-            parent_font_size: f32,
-            split_text: Option<&SplitText>,
-            extents: F,
-        ) -> Result<FontMetrics, Error>
-        where
-            F: FnOnce() -> Result<FontMetrics, Error>,
-        {
-            let extents = extents()?;
-            let font_size = match split_text {
-                Some(split_text) => {
-                    if let Some(style) = &split_text.style {
-                        style.font_size
-                    } else {
-                        parent_font_size
-                    }
+fn set_ellipsis(
+    text: &str,
+    fragments: &mut [Fragment],
+    style: &Style,
+    font: &Option<FontArc>,
+    textarea: &mut TextArea,
+    font_context: &FontContext,
+) -> Result<String, Error> {
+    fn rev_char_extents<F>(
+        is_newline: bool,
+        parent_font_size: f32,
+        split_text: Option<&SplitText>,
+        extents: F,
+    ) -> Result<FontMetrics, Error>
+    where
+        F: FnOnce() -> Result<FontMetrics, Error>,
+    {
+        let extents = extents()?;
+        let font_size = match split_text {
+            Some(split_text) => {
+                if let Some(style) = &split_text.style {
+                    style.font_size
+                } else {
+                    parent_font_size
                 }
-                None => parent_font_size,
-            };
-            if is_newline {
-                Ok(FontMetrics {
-                    height: extents.height,
-                    width: whitespace_width(font_size),
-                })
-            } else {
-                Ok(extents)
             }
+            None => parent_font_size,
+        };
+        if is_newline {
+            Ok(FontMetrics {
+                height: extents.height,
+                width: whitespace_width(font_size),
+            })
+        } else {
+            Ok(extents)
         }
+    }
 
-        let ellipsis = match &style.text_overflow {
-            TextOverflow::Ellipsis => "...",
-            TextOverflow::Content(s) => s,
-            TextOverflow::Clip => return Ok(text.to_string()),
-        };
+    let ellipsis = match &style.text_overflow {
+        TextOverflow::Ellipsis => "...",
+        TextOverflow::Content(s) => s,
+        TextOverflow::Clip => return Ok(text.to_string()),
+    };
 
-        let setting = FontSetting {
-            size: style.font_size,
-            letter_spacing: style.letter_spacing,
-            kern_setting: style.kern_setting,
-            is_pre: style.white_space.is_pre(),
-        };
+    let setting = FontSetting {
+        size: style.font_size,
+        letter_spacing: style.letter_spacing,
+        kern_setting: style.kern_setting,
+        is_pre: style.white_space.is_pre(),
+    };
 
-        let ellipsis_width = match font {
-            Some(font) if match_font_family('.', font) => {
-                font.text_extents(ellipsis, &setting).width
+    let ellipsis_width = match font {
+        Some(font) if match_font_family('.', font) => font.text_extents(ellipsis, &setting).width,
+        _ => {
+            let idx = font_context.select_font_family_with(style, '.')?;
+            font_context.with(&idx, |font| font.text_extents(ellipsis, &setting).width)?
+        }
+    };
+
+    let mut total_char_width = 0.;
+    let mut split_index = 0;
+    let mut chars = RevRenderingCharIndices::from_str(text);
+    while let Some((flags, i, ch, len)) = chars.next() {
+        let peek_char = chars.peek_char();
+        let is_newline = matches!(flags, Some(CharFlags::Newline));
+        let (split_text, _) = textarea.get_glyphs_from_char_range(i..i + len);
+        let extents = match font {
+            Some(font) if match_font_family(ch, font) => {
+                rev_char_extents(is_newline, style.font_size, split_text, || {
+                    textarea.char_extents(
+                        ch,
+                        peek_char,
+                        &flags,
+                        font,
+                        i..i + len,
+                        font_context,
+                        &setting,
+                    )
+                })?
             }
             _ => {
-                let idx = self.font_context.select_font_family('.')?;
-                self.font_context
-                    .with(&idx, |font| font.text_extents(ellipsis, &setting).width)
-            }
-        };
-
-        let mut total_char_width = 0.;
-        let mut split_index = 0;
-        let mut chars = RevRenderingCharIndices::from_str(text);
-        while let Some((flags, i, ch, len)) = chars.next() {
-            let peek_char = chars.peek_char();
-            let is_newline = matches!(flags, Some(CharFlags::Newline));
-            let (split_text, _) = textarea.get_glyphs_from_char_range(i..i + len);
-            let extents = match font {
-                Some(font) if match_font_family(ch, font) => {
+                let idx = font_context.select_font_family_with(style, ch)?;
+                font_context.with(&idx, |font| {
                     rev_char_extents(is_newline, style.font_size, split_text, || {
                         textarea.char_extents(
                             ch,
@@ -222,65 +303,204 @@ impl OGImageWriter {
                             &flags,
                             font,
                             i..i + len,
-                            &self.font_context,
+                            font_context,
                             &setting,
                         )
-                    })?
-                }
-                _ => {
-                    let idx = self.font_context.select_font_family(ch)?;
-                    self.font_context.with(&idx, |font| {
-                        rev_char_extents(is_newline, style.font_size, split_text, || {
-                            textarea.char_extents(
-                                ch,
-                                peek_char,
-                                &flags,
-                                font,
-                                i..i + len,
-                                &self.font_context,
-                                &setting,
-                            )
-                        })
-                    })?
+                    })
+                })??
+            }
+        };
+
+        total_char_width += extents.width;
+        if total_char_width >= ellipsis_width {
+            split_index = i;
+            break;
+        }
+    }
+
+    if let Some(fragment) = fragments.last_mut() {
+        // shape TextArea with ellipsis
+        while let Some(mut split_text) = textarea.0.pop() {
+            if split_text.range.start <= split_index && split_index <= split_text.range.end {
+                while let Some(mut glyph) = split_text.glyphs.pop() {
+                    if glyph.range.start <= split_index && split_index <= glyph.range.end {
+                        let end = glyph.range.end - split_index;
+                        glyph.range.end -= end;
+                        split_text.glyphs.push(glyph);
+                        break;
+                    }
                 }
-            };
 
-            total_char_width += extents.width;
-            if total_char_width >= ellipsis_width {
-                split_index = i;
+                let end = split_text.range.end - split_index;
+                split_text.range.end -= end;
+                split_text.text = (split_text.text[0..split_text.text.len() - end]).to_string();
+                textarea.0.push(split_text);
                 break;
             }
         }
 
-        if let Some(fragment) = fragments.last_mut() {
-            // shape TextArea with ellipsis
-            while let Some(mut split_text) = textarea.0.pop() {
-                if split_text.range.start <= split_index && split_index <= split_text.range.end {
-                    while let Some(mut glyph) = split_text.glyphs.pop() {
-                        if glyph.range.start <= split_index && split_index <= glyph.range.end {
-                            let end = glyph.range.end - split_index;
-                            glyph.range.end -= end;
-                            split_text.glyphs.push(glyph);
-                            break;
-                        }
-                    }
+        fragment.range = fragment.range.start..split_index + ellipsis.len();
+        let mut next_text = text[0..split_index].to_string();
+        next_text.push_str(ellipsis);
+        textarea.push_text_with_glyphs(ellipsis, font, style, font_context)?;
 
-                    let end = split_text.range.end - split_index;
-                    split_text.range.end -= end;
-                    split_text.text = (split_text.text[0..split_text.text.len() - end]).to_string();
-                    textarea.0.push(split_text);
-                    break;
-                }
-            }
+        return Ok(next_text);
+    }
 
-            fragment.range = fragment.range.start..split_index + ellipsis.len();
-            let mut next_text = text[0..split_index].to_string();
-            next_text.push_str(ellipsis);
-            textarea.push_text_with_glyphs(ellipsis, font, &self.font_context)?;
+    Ok(text.to_string())
+}
 
-            return Ok(next_text);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::img::ImageInputFormat;
+    use crate::layout_tree::test_utils::{ROBOTO, THUMBNAIL};
+    use crate::layout_tree::LayoutNode;
+    use crate::style::{BoxSizing, Padding, WindowStyle};
+    use crate::writer::OGImageWriter;
+
+    #[test]
+    fn test_layout_box_model() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer
+            .set_img_with_data(
+                THUMBNAIL,
+                100,
+                100,
+                ImageInputFormat::Png,
+                Style {
+                    padding: Padding(10, 20, 10, 20),
+                    width: Some(200),
+                    max_height: Some(50),
+                    ..Style::default()
+                },
+            )
+            .unwrap();
+        writer
+            .set_text(
+                "This is Open Graphic Image Writer for Web Developer.",
+                Style {
+                    font_size: 50.,
+                    padding: Padding(0, 50, 0, 50),
+                    width: Some(500),
+                    box_sizing: BoxSizing::BorderBox,
+                    ..Style::default()
+                },
+                Some(ROBOTO.to_vec()),
+            )
+            .unwrap();
+        let layout = writer.layout();
+
+        let img = &layout.children[0];
+        assert_eq!((img.rect.width, img.rect.height), (240, 70));
+
+        // Text is broken into lines in the content box.
+        let text = &layout.children[1];
+        assert!(text.fragments.len() > 1);
+        assert!(text.fragments.iter().all(|f| f.rect.x == 50));
+        assert!(text.fragments.iter().all(|f| f.rect.width <= 400));
+        assert_eq!(text.rect.x, 0);
+    }
+
+    #[test]
+    fn test_layout_text_max_width() {
+        let layout = |box_sizing| {
+            let mut writer = OGImageWriter::new(WindowStyle {
+                width: Some(1024),
+                height: Some(512),
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            writer
+                .set_text(
+                    "This is Open Graphic Image Writer for Web Developer.",
+                    Style {
+                        font_size: 50.,
+                        margin: Margin(0, 50, 0, 50),
+                        padding: Padding(0, 20, 0, 20),
+                        max_width: Some(400),
+                        box_sizing,
+                        ..Style::default()
+                    },
+                    Some(ROBOTO.to_vec()),
+                )
+                .unwrap();
+            writer.layout().children.remove(0)
+        };
+
+        // Margin is not included in max width.
+        let content_box = layout(BoxSizing::ContentBox);
+        let widest = |text: &LayoutNode| text.fragments.iter().map(|f| f.rect.width).max();
+        assert!(widest(&content_box).unwrap() > 300);
+        assert!(content_box.rect.width <= 440);
+
+        let border_box = layout(BoxSizing::BorderBox);
+        assert!(widest(&border_box).unwrap() <= 360);
+        assert!(border_box.rect.width <= 400);
+    }
+
+    #[test]
+    fn test_layout_text_taller_than_box() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer
+            .set_text(
+                "Hello World",
+                Style {
+                    font_size: 40.,
+                    height: Some(10),
+                    ..Style::default()
+                },
+                Some(ROBOTO.to_vec()),
+            )
+            .unwrap();
+        writer
+            .set_img_with_data(THUMBNAIL, 100, 100, ImageInputFormat::Png, Style::default())
+            .unwrap();
+        let layout = writer.layout();
+
+        // No line is shown, but the box keeps its height.
+        assert!(layout.children[0].fragments.is_empty());
+        assert!(layout.children[1].rect.y >= 10);
+        writer.paint().unwrap();
+    }
+
+    #[test]
+    fn test_layout_border() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer
+            .set_text(
+                "Hello",
+                Style {
+                    font_size: 50.,
+                    padding: Padding(10, 10, 10, 10),
+                    border_width: 5,
+                    ..Style::default()
+                },
+                Some(ROBOTO.to_vec()),
+            )
+            .unwrap();
+        let layout = writer.layout();
 
-        Ok(text.to_string())
+        // Border is placed between margin and padding.
+        let text = &layout.children[0];
+        let fragment = &text.fragments[0];
+        assert_eq!((fragment.rect.x, fragment.rect.y), (15, 15));
+        assert_eq!(text.rect.x, 0);
+        assert_eq!(text.rect.width, fragment.rect.width + 30);
     }
 }
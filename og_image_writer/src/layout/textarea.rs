@@ -1,18 +1,18 @@
 use crate::char::CharFlags;
-use crate::font::{create_font, FontArc, FontMetrics};
+use crate::font::{create_font_with_index, FontArc, FontMetrics};
 use crate::font_context::{FontContext, FontIndexStore};
 use crate::font_trait::Font;
 use crate::glyph::Glyph;
 use crate::renderer::FontSetting;
 use crate::style::Style;
 use crate::Error;
-use std::{ops::Range, str};
+use std::{ops::Range, str, sync::Arc};
 
 use super::split_text::SplitText;
 
 /// TextArea is box to store each text with style.
 /// For example you can set style to text one by one.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct TextArea(pub(super) Vec<SplitText>);
 
 impl TextArea {
@@ -22,14 +22,35 @@ impl TextArea {
 
     /// Push text with style.
     pub fn push(&mut self, text: &str, style: Style, font: Option<Vec<u8>>) -> Result<(), Error> {
+        self.push_with_font(text, style, font.map(|data| (data, 0)))
+    }
+
+    /// Push text with style and the face at `index` in TrueType/OpenType collection.
+    /// You can list faces in the collection with [collection_faces](crate::font_context::collection_faces).
+    pub fn push_with_index(
+        &mut self,
+        text: &str,
+        style: Style,
+        font: Vec<u8>,
+        index: u32,
+    ) -> Result<(), Error> {
+        self.push_with_font(text, style, Some((font, index)))
+    }
+
+    fn push_with_font(
+        &mut self,
+        text: &str,
+        style: Style,
+        font: Option<(Vec<u8>, u32)>,
+    ) -> Result<(), Error> {
         let last_range_end = match self.0.iter().last() {
             Some(split) => split.range.end,
             None => 0,
         };
 
-        let font: Option<Box<dyn Font>> = match font {
-            Some(font) => match create_font(font) {
-                Ok(font) => Some(Box::new(font)),
+        let font: Option<Arc<dyn Font>> = match font {
+            Some((font, index)) => match create_font_with_index(font, index) {
+                Ok(font) => Some(Arc::new(font)),
                 Err(_) => return Err(Error::InvalidFontBytes),
             },
             None => None,
@@ -78,6 +99,7 @@ impl TextArea {
         &mut self,
         text: &str,
         font: &Option<FontArc>,
+        parent_style: &Style,
         font_context: &FontContext,
     ) -> Result<(), Error> {
         let last_range_end = match self.0.iter().last() {
@@ -98,7 +120,7 @@ impl TextArea {
 
         let mut current_range_start = last_range_end;
 
-        split_text.set_glyphs(font, &mut current_range_start, font_context)?;
+        split_text.set_glyphs(font, parent_style, &mut current_range_start, font_context)?;
 
         self.0.push(split_text);
 
@@ -113,6 +135,15 @@ impl TextArea {
         text
     }
 
+    // Largest font size of texts. Text without style has the font size of `style`.
+    pub(crate) fn max_font_size(&self, style: &Style) -> f32 {
+        self.0
+            .iter()
+            .map(|split_text| split_text.style.as_ref().unwrap_or(style).font_size)
+            .reduce(f32::max)
+            .unwrap_or(style.font_size)
+    }
+
     pub(crate) fn get_glyphs_from_char_range(
         &self,
         range: Range<usize>,
@@ -126,14 +157,35 @@ impl TextArea {
         (None, None)
     }
 
+    // Return glyphs that overlap with `range` and the split text that has the glyph.
+    pub(crate) fn glyphs_in_range(
+        &self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = (&SplitText, &Glyph)> {
+        self.0.iter().flat_map(move |split_text| {
+            let range = range.clone();
+            split_text
+                .glyphs
+                .iter()
+                .filter(move |glyph| glyph.range.start < range.end && range.start < glyph.range.end)
+                .map(move |glyph| (split_text, glyph))
+        })
+    }
+
     pub(crate) fn set_glyphs(
         &mut self,
         parent_font: &Option<impl Font>,
+        parent_style: &Style,
         font_context: &FontContext,
     ) -> Result<(), Error> {
         let mut current_range_start = 0;
         for split_text in self.0.iter_mut() {
-            split_text.set_glyphs(parent_font, &mut current_range_start, font_context)?;
+            split_text.set_glyphs(
+                parent_font,
+                parent_style,
+                &mut current_range_start,
+                font_context,
+            )?;
         }
         Ok(())
     }
@@ -163,7 +215,7 @@ impl TextArea {
                 match &glyph.font_index_store {
                     FontIndexStore::Global(idx) => font_context.with(idx, |font| {
                         font.char_extents(cur_char, next_char, flags, &setting)
-                    }),
+                    })?,
                     FontIndexStore::Parent(_) => {
                         parent_font.char_extents(cur_char, next_char, flags, &setting)
                     }
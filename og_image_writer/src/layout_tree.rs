@@ -0,0 +1,318 @@
+//! Computed layout of elements.
+//!
+//! You can read the position of each element computed by the writer with [OGImageWriter::layout](crate::writer::OGImageWriter::layout).
+//! It is useful to build image map, debug alignment or place overlay.
+//! Enable `serde` feature to serialize the layout, for example into JSON.
+
+use crate::element::{Element, Fragment, Rect, Text};
+use crate::font_context::FontIndexStore;
+use crate::font_trait::Font;
+use crate::style::{Margin, Padding, WindowStyle};
+use crate::writer::Tree;
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Rectangle in window coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct LayoutRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl LayoutRect {
+    pub(super) fn from_rect(rect: &Rect) -> LayoutRect {
+        LayoutRect {
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+        }
+    }
+
+    fn expand(&self, margin: &Margin) -> LayoutRect {
+        let Margin(top, right, bottom, left) = *margin;
+        LayoutRect {
+            x: self.x - left,
+            y: self.y - top,
+            width: (self.width as i32 + left + right).max(0) as u32,
+            height: (self.height as i32 + top + bottom).max(0) as u32,
+        }
+    }
+}
+
+/// Kind of element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "lowercase"))]
+pub enum LayoutKind {
+    Img,
+    Text,
+    Container,
+}
+
+/// Line of text element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct LayoutFragment {
+    /// Byte range in the text of the element.
+    pub range: Range<usize>,
+    pub text: String,
+    pub rect: LayoutRect,
+    /// Y coordinate of the baseline. The tallest ascent is used when the line has multiple fonts.
+    pub baseline: i32,
+}
+
+/// Computed layout of an element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct LayoutNode {
+    pub kind: LayoutKind,
+    /// Border box of the element.
+    pub rect: LayoutRect,
+    /// Border box with margin.
+    pub margin_box: LayoutRect,
+    /// Lines of text element. This is empty for the other elements.
+    pub fragments: Vec<LayoutFragment>,
+    /// Children of container element. This is empty for the other elements.
+    pub children: Vec<LayoutNode>,
+}
+
+impl LayoutNode {
+    // `tree` must be processed by `OGImageWriter::process_tree`.
+    fn from_tree(tree: &Tree) -> Vec<LayoutNode> {
+        tree.0.iter().filter_map(LayoutNode::new).collect()
+    }
+
+    fn new(elm: &Element) -> Option<LayoutNode> {
+        match elm {
+            Element::Img(Some(img)) => {
+                let rect = LayoutRect::from_rect(&img.rect);
+                Some(LayoutNode {
+                    kind: LayoutKind::Img,
+                    rect,
+                    margin_box: rect.expand(&img.style.margin),
+                    fragments: vec![],
+                    children: vec![],
+                })
+            }
+            Element::Text(Some(text)) => {
+                let fragments: Vec<LayoutFragment> = text
+                    .fragments
+                    .iter()
+                    .map(|fragment| LayoutFragment {
+                        range: fragment.range.clone(),
+                        text: text.text[fragment.range.clone()].to_string(),
+                        rect: LayoutRect::from_rect(&fragment.rect),
+                        baseline: fragment_baseline(text, fragment),
+                    })
+                    .collect();
+                let Padding(top, right, bottom, left) = text.style.inset();
+                let rect = LayoutRect::from_rect(&text.content_rect()).expand(&Margin(
+                    top as i32,
+                    right as i32,
+                    bottom as i32,
+                    left as i32,
+                ));
+                Some(LayoutNode {
+                    kind: LayoutKind::Text,
+                    rect,
+                    margin_box: rect.expand(&text.style.margin),
+                    fragments,
+                    children: vec![],
+                })
+            }
+            Element::Container(Some(container)) => {
+                let rect = LayoutRect::from_rect(&container.rect);
+                Some(LayoutNode {
+                    kind: LayoutKind::Container,
+                    rect,
+                    margin_box: rect.expand(&container.style.margin),
+                    fragments: vec![],
+                    children: LayoutNode::from_tree(&container.tree),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+// Glyphs are drawn from the top of the fragment, so baseline is placed at the ascent of the font.
+fn fragment_baseline(text: &Text, fragment: &Fragment) -> i32 {
+    let ascent = text
+        .textarea
+        .glyphs_in_range(fragment.range.clone())
+        .map(|(split_text, glyph)| {
+            let size = split_text.style.as_ref().unwrap_or(&text.style).font_size;
+            match &glyph.font_index_store {
+                FontIndexStore::Global(idx) => text
+                    .font_context
+                    .with(idx, |font| font.ascent(size))
+                    .unwrap_or(0.),
+                FontIndexStore::Parent(_) => {
+                    text.font.as_ref().map_or(0., |font| font.ascent(size))
+                }
+                FontIndexStore::Child(_) => split_text
+                    .font
+                    .as_ref()
+                    .map_or(0., |font| font.ascent(size)),
+            }
+        })
+        .fold(0., f32::max);
+    fragment.rect.y + ascent as i32
+}
+
+/// Computed layout of the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct LayoutTree {
+    pub width: u32,
+    pub height: u32,
+    /// Elements in the order they were set.
+    pub children: Vec<LayoutNode>,
+}
+
+impl LayoutTree {
+    // `tree` must be processed by `OGImageWriter::process_tree`.
+    pub(super) fn new(window: &WindowStyle, tree: &Tree) -> LayoutTree {
+        LayoutTree {
+            width: window.resolved_width(),
+            height: window.resolved_height(),
+            children: LayoutNode::from_tree(tree),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use super::*;
+    use crate::img::ImageInputFormat;
+    use crate::style::Style;
+    use crate::writer::OGImageWriter;
+
+    pub(crate) const ROBOTO: &[u8] = include_bytes!("../../fonts/Roboto-Light.ttf");
+    pub(crate) const THUMBNAIL: &[u8] = include_bytes!("../../assets/thumbnail_circle.png");
+
+    // Writer that has an image and text in `window`.
+    pub(crate) fn writer_with_window(window: WindowStyle) -> OGImageWriter {
+        let mut writer = OGImageWriter::new(window).unwrap();
+        writer
+            .set_img_with_data(
+                THUMBNAIL,
+                100,
+                100,
+                ImageInputFormat::Png,
+                Style {
+                    margin: Margin(10, 0, 20, 0),
+                    ..Style::default()
+                },
+            )
+            .unwrap();
+        writer
+            .set_text(
+                "Hello World",
+                Style {
+                    font_size: 50.,
+                    ..Style::default()
+                },
+                Some(ROBOTO.to_vec()),
+            )
+            .unwrap();
+        writer
+    }
+
+    // Lay out an image of 100x100 for each of `styles` in `window`, and return the rect of each image.
+    pub(crate) fn layout_images(window: WindowStyle, styles: &[Style]) -> Vec<LayoutRect> {
+        let mut writer = OGImageWriter::new(window).unwrap();
+        for style in styles {
+            writer
+                .set_img_with_data(THUMBNAIL, 100, 100, ImageInputFormat::Png, style.clone())
+                .unwrap();
+        }
+        writer
+            .layout()
+            .children
+            .into_iter()
+            .map(|node| node.rect)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::writer_with_window;
+    use super::*;
+    use crate::style::{AlignItems, JustifyContent};
+
+    fn writer(justify_content: JustifyContent) -> crate::writer::OGImageWriter {
+        writer_with_window(WindowStyle {
+            width: Some(1024),
+            height: Some(512),
+            align_items: AlignItems::Center,
+            justify_content,
+            ..WindowStyle::default()
+        })
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut writer = writer(JustifyContent::Start);
+        let layout = writer.layout();
+
+        assert_eq!((layout.width, layout.height), (1024, 512));
+        assert_eq!(layout.children.len(), 2);
+
+        let img = &layout.children[0];
+        assert_eq!(img.kind, LayoutKind::Img);
+        assert_eq!(
+            img.rect,
+            LayoutRect {
+                x: 462,
+                y: 10,
+                width: 100,
+                height: 100
+            }
+        );
+        assert_eq!(
+            img.margin_box,
+            LayoutRect {
+                x: 462,
+                y: 0,
+                width: 100,
+                height: 130
+            }
+        );
+
+        let text = &layout.children[1];
+        assert_eq!(text.kind, LayoutKind::Text);
+        assert_eq!(text.fragments.len(), 1);
+        assert_eq!(text.fragments[0].text, "Hello World");
+        assert_eq!(text.rect, text.fragments[0].rect);
+        let baseline = text.fragments[0].baseline;
+        assert!(text.rect.y < baseline);
+        assert!(text.rect.y >= img.margin_box.y + img.margin_box.height as i32);
+
+        // Layout is computed without changing the writer.
+        assert_eq!(writer.layout(), layout);
+        writer.paint().unwrap();
+    }
+
+    #[test]
+    fn test_layout_order() {
+        let layout = writer(JustifyContent::End).layout();
+        assert_eq!(layout.children[0].kind, LayoutKind::Img);
+        assert!(layout.children[0].rect.y < layout.children[1].rect.y);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_layout() {
+        let layout = writer(JustifyContent::Start).layout();
+        let json = serde_json::to_value(&layout).unwrap();
+        assert_eq!(json["children"][0]["kind"], "img");
+        assert_eq!(json["children"][1]["fragments"][0]["range"]["end"], 11);
+    }
+}
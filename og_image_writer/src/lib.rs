@@ -57,8 +57,8 @@
 //!
 //! fn main() -> anyhow::Result<()> {
 //!    let mut writer = OGImageWriter::new(style::WindowStyle {
-//!        width: 1024,
-//!        height: 512,
+//!        width: Some(1024),
+//!        height: Some(512),
 //!        background_color: Some(style::Rgba([70, 40, 90, 255])),
 //!        align_items: style::AlignItems::Center,
 //!        justify_content: style::JustifyContent::Center,
@@ -66,8 +66,10 @@
 //!    })?;
 //!
 //!    // Set global fallback fonts.
-//!    let mut fc = FontContext::new();
+//!    let fc = FontContext::new();
 //!    fc.push(Vec::from(include_bytes!("../../fonts/Mplus1-Black.ttf") as &[u8]))?;
+//!    // FontContext can be cloned cheaply and shared with other writers and threads.
+//!    writer.set_font_context(&fc);
 //!
 //!    // Set style for each text.
 //!    let mut textarea = TextArea::new();
@@ -124,19 +126,27 @@
 
 pub mod element;
 pub mod font_context;
+pub mod glyph_cache;
 pub mod img;
+pub mod layout_tree;
+pub mod measure;
 pub mod style;
 pub mod writer;
 pub use context::ImageOutputFormat;
 pub use error::Error;
-pub use layout::TextArea;
+pub use layout::{LayoutEngine, TextArea};
 
 mod char;
 mod context;
+mod debug;
+mod decoration;
 mod error;
 mod font;
+mod font_info;
 mod font_trait;
 mod glyph;
 mod layout;
 mod line_breaker;
 mod renderer;
+mod shape;
+mod woff;
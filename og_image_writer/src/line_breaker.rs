@@ -93,7 +93,7 @@ impl<'a> LineBreaker<'a> {
                     &setting,
                 )?,
                 _ => {
-                    let idx = font_context.select_font_family('.')?;
+                    let idx = font_context.select_font_family_with(style, '.')?;
                     font_context.with(&idx, |font| {
                         textarea.char_extents(
                             ch,
@@ -104,7 +104,7 @@ impl<'a> LineBreaker<'a> {
                             font_context,
                             &setting,
                         )
-                    })?
+                    })??
                 }
             };
 
@@ -129,10 +129,10 @@ impl<'a> LineBreaker<'a> {
 
             if width <= line_width + ch_width {
                 match style.word_break {
-                    WordBreak::Normal => {
+                    // Line is broken only at whitespace, so word that is wider than `width` overflows.
+                    WordBreak::Normal if last_whitespace_idx > range.start => {
                         let end = range.end;
                         line_width -= word_width + whitespace_width;
-                        // TODO: support overflow text when text can not be broken.
                         self.lines.push(Line::new(
                             range.start..last_whitespace_idx,
                             line_width,
@@ -146,7 +146,7 @@ impl<'a> LineBreaker<'a> {
                         line_width = word_width;
                         line_height = 0.;
                     }
-                    WordBreak::BreakAll => {
+                    WordBreak::BreakAll if !range.is_empty() => {
                         let start = range.end;
                         self.lines.push(Line::new(range, line_width, line_height));
                         self.set_max_line_size(FontMetrics {
@@ -157,6 +157,7 @@ impl<'a> LineBreaker<'a> {
                         line_width = 0.;
                         line_height = 0.;
                     }
+                    _ => {}
                 }
             }
 
@@ -233,7 +234,7 @@ mod tests {
         let font_context = FontContext::new();
 
         textarea
-            .set_glyphs(&Some(font.clone()), &font_context)
+            .set_glyphs(&Some(font.clone()), &Style::default(), &font_context)
             .unwrap();
 
         let mut line_breaker = LineBreaker::new(text);
@@ -279,7 +280,7 @@ mod tests {
         let font_context = FontContext::new();
 
         textarea
-            .set_glyphs(&Some(font.clone()), &font_context)
+            .set_glyphs(&Some(font.clone()), &Style::default(), &font_context)
             .unwrap();
 
         let mut line_breaker = LineBreaker::new(text);
@@ -326,7 +327,7 @@ mod tests {
         let font_context = FontContext::new();
 
         textarea
-            .set_glyphs(&Some(font.clone()), &font_context)
+            .set_glyphs(&Some(font.clone()), &Style::default(), &font_context)
             .unwrap();
 
         let mut line_breaker = LineBreaker::new(text);
@@ -383,7 +384,7 @@ mod tests {
         let font_context = FontContext::new();
 
         textarea
-            .set_glyphs(&Some(font.clone()), &font_context)
+            .set_glyphs(&Some(font.clone()), &Style::default(), &font_context)
             .unwrap();
 
         let mut line_breaker = LineBreaker::new(text);
@@ -424,7 +425,7 @@ mod tests {
         let font_context = FontContext::new();
 
         textarea
-            .set_glyphs(&Some(font.clone()), &font_context)
+            .set_glyphs(&Some(font.clone()), &Style::default(), &font_context)
             .unwrap();
 
         let mut line_breaker = LineBreaker::new(text);
@@ -454,4 +455,42 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_break_text_with_long_word() {
+        let text = "Hello World";
+        let mut textarea = TextArea::new();
+        textarea.push_text(text);
+
+        let font = FontMock::new(None);
+
+        let font_context = FontContext::new();
+
+        textarea
+            .set_glyphs(&Some(font.clone()), &Style::default(), &font_context)
+            .unwrap();
+
+        let mut line_breaker = LineBreaker::new(text);
+        line_breaker
+            .break_text(
+                20.,
+                &Style {
+                    font_size: 10.,
+                    word_break: WordBreak::Normal,
+                    ..Style::default()
+                },
+                &Some(font),
+                &textarea,
+                &font_context,
+            )
+            .unwrap();
+
+        // Word that is wider than width overflows the line.
+        let expects = ["Hello ", "World"];
+
+        assert_eq!(line_breaker.lines.len(), expects.len());
+        for (i, line) in line_breaker.lines.iter().enumerate() {
+            assert_eq!(expects[i], &text[line.range.clone()]);
+        }
+    }
 }
@@ -0,0 +1,202 @@
+//! Measure text without painting.
+//!
+//! Text is broken into lines in the same way as [OGImageWriter::set_text](crate::writer::OGImageWriter::set_text),
+//! so you can know how many lines text wraps to and whether text is ellipsized before writing it.
+//!
+//! ```rust
+//! use og_image_writer::{font_context::FontContext, measure::measure_text, style};
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let font = Vec::from(include_bytes!("../../fonts/Roboto-Light.ttf") as &[u8]);
+//!     let measurement = measure_text(
+//!         "This is Open Graphic Image Writer for Web Developer.",
+//!         &style::Style {
+//!             font_size: 50.,
+//!             max_height: Some(100),
+//!             ..style::Style::default()
+//!         },
+//!         Some(font),
+//!         500,
+//!         &FontContext::new(),
+//!     )?;
+//!
+//!     println!("{} lines, {}px", measurement.lines.len(), measurement.height);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::font::create_font;
+use crate::font_context::FontContext;
+use crate::layout::{layout_text, text_area_width, TextArea, TextLayout};
+use crate::style::Style;
+use crate::Error;
+use std::ops::Range;
+
+/// Line of measured text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeasuredLine {
+    /// Byte range in [TextMeasurement::text].
+    pub range: Range<usize>,
+    /// Offset from the top of the text.
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Result of measuring text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMeasurement {
+    /// Text to be rendered. This includes ellipsis when text is overflowed.
+    pub text: String,
+    /// Lines that fit in `Style::max_height`. This is empty if even the first line does not fit.
+    pub lines: Vec<MeasuredLine>,
+    /// Width of the longest line.
+    pub width: f32,
+    /// Total height of lines.
+    pub height: f32,
+    /// Height of the tallest line.
+    pub line_height: f32,
+    /// True if text overflows `Style::max_height` and is truncated.
+    pub is_overflow: bool,
+}
+
+impl From<TextLayout> for TextMeasurement {
+    fn from(layout: TextLayout) -> Self {
+        TextMeasurement {
+            text: layout.text,
+            lines: layout
+                .lines
+                .into_iter()
+                .map(|fragment| MeasuredLine {
+                    range: fragment.range,
+                    y: fragment.rect.y as u32,
+                    width: fragment.rect.width,
+                    height: fragment.rect.height,
+                })
+                .collect(),
+            width: layout.max_line_width,
+            height: layout.total_height,
+            line_height: layout.max_line_height,
+            is_overflow: layout.is_overflow,
+        }
+    }
+}
+
+/// Measure text laid out in `width` like window width.
+/// `Style::max_width` and horizontal margin are applied in the same way as the writer.
+pub fn measure_text(
+    text: &str,
+    style: &Style,
+    font: Option<Vec<u8>>,
+    width: u32,
+    font_context: &FontContext,
+) -> Result<TextMeasurement, Error> {
+    let mut textarea = TextArea::new();
+    textarea.push_text(text);
+    measure_textarea(&textarea, style, font, width, font_context)
+}
+
+/// Measure [TextArea] laid out in `width` like window width.
+pub fn measure_textarea(
+    textarea: &TextArea,
+    style: &Style,
+    font: Option<Vec<u8>>,
+    width: u32,
+    font_context: &FontContext,
+) -> Result<TextMeasurement, Error> {
+    let font = match font {
+        Some(data) => Some(create_font(data)?),
+        None => None,
+    };
+
+    // TextArea is shaped with ellipsis, so measure copied one.
+    let mut textarea = textarea.clone();
+    let layout = layout_text(
+        &mut textarea,
+        style,
+        &font,
+        text_area_width(style, width) as f32,
+        font_context,
+    )?;
+
+    Ok(layout.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::TextOverflow;
+
+    const ROBOTO: &[u8] = include_bytes!("../../fonts/Roboto-Light.ttf");
+
+    #[test]
+    fn test_measure_text() {
+        let text = "This is Open Graphic Image Writer for Web Developer.";
+        let font_context = FontContext::new();
+        let style = Style {
+            font_size: 50.,
+            ..Style::default()
+        };
+
+        let single =
+            measure_text(text, &style, Some(ROBOTO.to_vec()), 10000, &font_context).unwrap();
+        assert_eq!(single.lines.len(), 1);
+        assert_eq!(single.text, text);
+        assert!(!single.is_overflow);
+
+        let wrapped =
+            measure_text(text, &style, Some(ROBOTO.to_vec()), 500, &font_context).unwrap();
+        assert!(wrapped.lines.len() > 1);
+        assert!(wrapped.height > single.height);
+        assert!(wrapped.lines.iter().all(|line| line.width <= 500));
+        assert_eq!(wrapped.lines.last().unwrap().range.end, text.len());
+    }
+
+    #[test]
+    fn test_measure_overflow() {
+        let text = "This is Open Graphic Image Writer for Web Developer.";
+        let font_context = FontContext::new();
+        font_context.push(ROBOTO.to_vec()).unwrap();
+        let style = Style {
+            font_size: 50.,
+            max_height: Some(60),
+            text_overflow: TextOverflow::Ellipsis,
+            ..Style::default()
+        };
+
+        let mut textarea = TextArea::new();
+        textarea.push_text(text);
+        let measurement = measure_textarea(&textarea, &style, None, 500, &font_context).unwrap();
+        assert!(measurement.is_overflow);
+        assert_eq!(measurement.lines.len(), 1);
+        assert!(measurement.text.ends_with("..."));
+
+        // TextArea is not changed by measuring.
+        let again = measure_textarea(&textarea, &style, None, 500, &font_context).unwrap();
+        assert_eq!(measurement, again);
+    }
+
+    #[test]
+    fn test_measure_overflow_first_line() {
+        let style = Style {
+            font_size: 40.,
+            max_height: Some(10),
+            ..Style::default()
+        };
+
+        // Max height is smaller than one line, so no line is shown.
+        let measurement = measure_text(
+            "Hello World",
+            &style,
+            Some(ROBOTO.to_vec()),
+            500,
+            &FontContext::new(),
+        )
+        .unwrap();
+        assert!(measurement.is_overflow);
+        assert!(measurement.lines.is_empty());
+        assert_eq!(measurement.text, "");
+        assert_eq!(measurement.height, 0.);
+    }
+}
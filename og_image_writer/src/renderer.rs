@@ -2,14 +2,12 @@ use super::char::RenderingCharIndices;
 use super::font::whitespace_width;
 use super::font_trait::Font;
 use super::style::KernSetting;
-use ab_glyph::{point, Glyph};
 use conv::ValueInto;
 use image::Pixel;
 use imageproc::definitions::Clamp;
 use imageproc::drawing::Canvas;
 use imageproc::pixelops::weighted_sum;
 use std::f32;
-use std::i32;
 
 #[derive(Clone)]
 pub struct FontSetting {
@@ -31,14 +29,18 @@ impl Default for FontSetting {
 }
 
 /// Draws colored text on an image in place. `scale` is augmented font scaling on both the x and y axis (in pixels). Note that this function *does not* support newlines, you must do this manually
+///
+/// `clip` returns how much of the pixel at the position is visible.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn draw_text_mut<'a, C>(
     canvas: &'a mut C,
     color: C::Pixel,
-    x: u32,
-    y: u32,
+    x: i32,
+    y: i32,
     font: &'a dyn Font,
     setting: &FontSetting,
     text: &'a str,
+    clip: impl Fn(u32, u32) -> f32,
 ) where
     C: Canvas,
     <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
@@ -57,10 +59,9 @@ pub(super) fn draw_text_mut<'a, C>(
         }
 
         let glyph_id = font.glyph_id(ch);
-        let q_glyph: Glyph = glyph_id.with_scale_and_position(setting.size, point(0., 0.));
-        if let Some(q) = font.outline_glyph(q_glyph, setting.size) {
-            let bb = q.px_bounds();
-            q.draw(|gx, gy, gv| {
+        if let Some(q) = font.rasterize_glyph(glyph_id, setting.size) {
+            let bb = q.bounds;
+            q.for_each_pixel(|gx, gy, gv| {
                 let mut gx = gx as i32 + current_x;
                 if let KernSetting::Normal = setting.kern_setting {
                     gx += bb.min.x as i32;
@@ -69,16 +70,18 @@ pub(super) fn draw_text_mut<'a, C>(
                 let y_bearing = (bb.min.y + font.ascent(setting.size)) as i32;
                 let gy = gy as i32 + y_bearing;
 
-                let image_x = gx + x as i32;
-                let image_y = gy + y as i32;
+                let image_x = gx + x;
+                let image_y = gy + y;
 
                 let image_width = canvas.width() as i32;
                 let image_height = canvas.height() as i32;
 
                 if image_x >= 0 && image_x < image_width && image_y >= 0 && image_y < image_height {
-                    let pixel = canvas.get_pixel(image_x as u32, image_y as u32);
+                    let (image_x, image_y) = (image_x as u32, image_y as u32);
+                    let gv = gv * clip(image_x, image_y);
+                    let pixel = canvas.get_pixel(image_x, image_y);
                     let weighted_color = weighted_sum(pixel, color, 1.0 - gv, gv);
-                    canvas.draw_pixel(image_x as u32, image_y as u32, weighted_color);
+                    canvas.draw_pixel(image_x, image_y, weighted_color);
                 }
             });
 
@@ -0,0 +1,107 @@
+use super::element::Rect;
+use super::style::BorderRadius;
+
+// Rounded rectangle that is inset by `inset` from `rect`.
+#[derive(Clone, Copy)]
+pub(super) struct Shape {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    // top left, top right, bottom right, bottom left
+    radius: [f32; 4],
+}
+
+impl Shape {
+    pub(super) fn new(rect: &Rect, radius: &BorderRadius, inset: u32) -> Self {
+        let width = rect.width.saturating_sub(inset * 2) as f32;
+        let height = rect.height.saturating_sub(inset * 2) as f32;
+        let max = width.min(height) / 2.;
+        let BorderRadius(tl, tr, br, bl) = *radius;
+        let radius = [tl, tr, br, bl].map(|r| (r.saturating_sub(inset) as f32).min(max));
+        Shape {
+            x: (rect.x + inset as i32) as f32,
+            y: (rect.y + inset as i32) as f32,
+            width,
+            height,
+            radius,
+        }
+    }
+
+    pub(super) fn translate(&self, dx: f32, dy: f32) -> Shape {
+        Shape {
+            x: self.x + dx,
+            y: self.y + dy,
+            ..*self
+        }
+    }
+
+    // Grow shape by `spread` on each side. Rounded corners are grown too like CSS.
+    pub(super) fn spread(&self, spread: f32) -> Shape {
+        let width = (self.width + spread * 2.).max(0.);
+        let height = (self.height + spread * 2.).max(0.);
+        let max = width.min(height) / 2.;
+        let radius = self.radius.map(|r| match r {
+            r if r > 0. => (r + spread).clamp(0., max),
+            _ => 0.,
+        });
+        Shape {
+            x: self.x - spread,
+            y: self.y - spread,
+            width,
+            height,
+            radius,
+        }
+    }
+
+    // Return signed distance from edge of the shape to the center of the pixel.
+    // Distance is negative inside the shape.
+    fn distance(&self, x: u32, y: u32) -> f32 {
+        let (half_width, half_height) = (self.width / 2., self.height / 2.);
+        let px = x as f32 + 0.5 - self.x - half_width;
+        let py = y as f32 + 0.5 - self.y - half_height;
+        let r = match (px < 0., py < 0.) {
+            (true, true) => self.radius[0],
+            (false, true) => self.radius[1],
+            (false, false) => self.radius[2],
+            (true, false) => self.radius[3],
+        };
+        let qx = px.abs() - half_width + r;
+        let qy = py.abs() - half_height + r;
+        let outside = qx.max(0.).hypot(qy.max(0.));
+        outside + qx.max(qy).min(0.) - r
+    }
+
+    // Return how much of the pixel is covered by the shape, from `0` to `1`.
+    // Edges are antialiased with signed distance from the center of the pixel.
+    pub(super) fn coverage(&self, x: u32, y: u32) -> f32 {
+        if self.width == 0. || self.height == 0. {
+            return 0.;
+        }
+        (0.5 - self.distance(x, y)).clamp(0., 1.)
+    }
+
+    // Return coverage of the shape that is blurred by gaussian with `sigma`.
+    // Blur is approximated by distance from the edge, that is exact for straight edges.
+    pub(super) fn blurred_coverage(&self, x: u32, y: u32, sigma: f32) -> f32 {
+        if sigma == 0. {
+            return self.coverage(x, y);
+        }
+        if self.width == 0. || self.height == 0. {
+            return 0.;
+        }
+        0.5 - 0.5 * erf(self.distance(x, y) / (sigma * std::f32::consts::SQRT_2))
+    }
+}
+
+// Approximation of error function with maximum error of 1.5e-7.
+// See Abramowitz and Stegun, formula 7.1.26.
+fn erf(x: f32) -> f32 {
+    let t = 1. / (1. + 0.327_591_1 * x.abs());
+    let y = 1.
+        - (((((1.061_405_4 * t - 1.453_152_1) * t) + 1.421_413_7) * t - 0.284_496_72) * t
+            + 0.254_829_6)
+            * t
+            * (-x * x).exp();
+    y.copysign(x)
+}
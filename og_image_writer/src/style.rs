@@ -68,12 +68,106 @@ impl WhiteSpace {
     }
 }
 
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[derive(Debug, Copy, Clone)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Margin(pub i32, pub i32, pub i32, pub i32);
 
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Padding(pub u32, pub u32, pub u32, pub u32);
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct BorderRadius(pub u32, pub u32, pub u32, pub u32);
 
+/// Position of image in its content box when image is smaller or larger than the box.
+/// Horizontal and vertical ratio, `0.` aligns image to the start and `1.` aligns to the end.
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectPosition(pub f32, pub f32);
+
+impl Default for ObjectPosition {
+    fn default() -> Self {
+        ObjectPosition(0.5, 0.5)
+    }
+}
+
+/// Shadow that follows `border_radius` of the element.
+/// Shadow is painted outside of the border box, or inside of the padding box if `inset` is `true`.
+#[derive(Debug, Clone, Copy)]
+pub struct BoxShadow {
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// Shadow is blurred over this length across its edge.
+    pub blur: u32,
+    /// Shadow is grown by this length. Negative value shrinks shadow.
+    pub spread: i32,
+    pub color: Rgba,
+    pub inset: bool,
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        BoxShadow {
+            offset_x: 0,
+            offset_y: 0,
+            blur: 0,
+            spread: 0,
+            color: Rgba([0, 0, 0, 255]),
+            inset: false,
+        }
+    }
+}
+
+/// Controls whether `width` and `height` include padding and border.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum BoxSizing {
+    ContentBox,
+    BorderBox,
+}
+
+/// Controls whether `width` and `height` include padding and border.
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[derive(Debug, Copy, Clone)]
+pub enum BoxSizing {
+    ContentBox,
+    BorderBox,
+}
+
+/// Line style of border.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum BorderStyle {
+    None,
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+/// Line style of border.
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[derive(Debug, Copy, Clone)]
+pub enum BorderStyle {
+    None,
+    Solid,
+    Dashed,
+    Dotted,
+}
+
 /// Adjust the horizontal position.
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 #[wasm_bindgen]
@@ -101,6 +195,12 @@ pub enum JustifyContent {
     Start,
     Center,
     End,
+    /// Items are evenly distributed. The first item is at the start and the last item is at the end.
+    SpaceBetween,
+    /// Items are evenly distributed with half-size space on either end.
+    SpaceAround,
+    /// Items are evenly distributed with equal space around them.
+    SpaceEvenly,
 }
 
 #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
@@ -110,6 +210,22 @@ pub enum JustifyContent {
     Start,
     Center,
     End,
+    /// Items are evenly distributed. The first item is at the start and the last item is at the end.
+    SpaceBetween,
+    /// Items are evenly distributed with half-size space on either end.
+    SpaceAround,
+    /// Items are evenly distributed with equal space around them.
+    SpaceEvenly,
+}
+
+impl JustifyContent {
+    // Space is distributed between items.
+    pub(crate) fn is_distributed(&self) -> bool {
+        matches!(
+            self,
+            Self::SpaceBetween | Self::SpaceAround | Self::SpaceEvenly
+        )
+    }
 }
 
 /// Adjust the text horizontal position.
@@ -130,7 +246,7 @@ pub enum TextAlign {
     End,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum TextOverflow {
     Clip,
     Ellipsis,
@@ -158,6 +274,10 @@ pub enum Position {
 pub enum FlexDirection {
     Column,
     Row,
+    /// Items are laid out from the bottom to the top.
+    ColumnReverse,
+    /// Items are laid out from the right to the left.
+    RowReverse,
 }
 
 #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
@@ -165,13 +285,126 @@ pub enum FlexDirection {
 pub enum FlexDirection {
     Column,
     Row,
+    /// Items are laid out from the bottom to the top.
+    ColumnReverse,
+    /// Items are laid out from the right to the left.
+    RowReverse,
+}
+
+impl FlexDirection {
+    pub(crate) fn is_column(&self) -> bool {
+        matches!(self, Self::Column | Self::ColumnReverse)
+    }
+
+    // Items are laid out from the end of main axis.
+    pub(crate) fn is_reverse(&self) -> bool {
+        matches!(self, Self::ColumnReverse | Self::RowReverse)
+    }
+}
+
+/// Controls whether items are wrapped onto multiple lines when they overflow the main axis of the window.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum FlexWrap {
+    NoWrap,
+    Wrap,
+    WrapReverse,
+}
+
+/// Controls whether items are wrapped onto multiple lines when they overflow the main axis of the window.
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[derive(Debug, Copy, Clone)]
+pub enum FlexWrap {
+    NoWrap,
+    Wrap,
+    WrapReverse,
+}
+
+/// Controls whether children that overflow the container are painted.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum Overflow {
+    Visible,
+    Hidden,
+}
+
+/// Controls whether children that overflow the container are painted.
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[derive(Debug, Copy, Clone)]
+pub enum Overflow {
+    Visible,
+    Hidden,
+}
+
+/// Adjust the position of lines in the cross axis when items are wrapped.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum AlignContent {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    Stretch,
+}
+
+/// Adjust the position of lines in the cross axis when items are wrapped.
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[derive(Debug, Copy, Clone)]
+pub enum AlignContent {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    Stretch,
+}
+
+/// Controls how image is resized to fit in its content box.
+/// `Contain` and `ScaleDown` keep the whole image and leave the rest of the box transparent,
+/// `Cover` fills the box and crops the image, `Fill` stretches the image, and `None` keeps the original size.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub enum ObjectFit {
+    Contain,
+    Cover,
+    Fill,
+    ScaleDown,
+    None,
+}
+
+/// Controls how image is resized to fit in its content box.
+/// `Contain` and `ScaleDown` keep the whole image and leave the rest of the box transparent,
+/// `Cover` fills the box and crops the image, `Fill` stretches the image, and `None` keeps the original size.
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[derive(Debug, Copy, Clone)]
+pub enum ObjectFit {
+    Contain,
+    Cover,
+    Fill,
+    ScaleDown,
+    None,
 }
 
 /// Style is used by `text` or `img` element.
 /// Text element is `inline-block`, so you can adjust text position by using `text_align`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Style {
     pub margin: Margin,
+    /// Space between the border and the content.
+    pub padding: Padding,
+    /// Width of the element. Size of the content is used by default.
+    pub width: Option<u32>,
+    /// Height of the element. Size of the content is used by default.
+    pub height: Option<u32>,
+    pub min_width: Option<u32>,
+    pub min_height: Option<u32>,
+    /// Controls whether sizes include padding and border.
+    pub box_sizing: BoxSizing,
     /// For Text element
     pub line_height: f32,
     /// For Text element
@@ -187,27 +420,73 @@ pub struct Style {
     /// For Text element
     pub color: Rgba,
     /// For Text element
-    pub text_align: TextAlign,
+    /// List of family names registered by [FontContext::register](crate::font_context::FontContext::register).
+    /// Fonts are looked up in order, and global fallback fonts are used when no family has the glyph.
+    pub font_family: Vec<String>,
     /// For Text element
-    pub max_height: Option<u32>,
+    /// Weight used to select font from `font_family`. `400` is normal and `700` is bold.
+    pub font_weight: u16,
+    /// For Text element
+    /// Style used to select font from `font_family`.
+    pub font_style: FontStyle,
     /// For Text element
+    pub text_align: TextAlign,
+    /// Text is truncated to this height.
+    pub max_height: Option<u32>,
+    /// Maximum width of the element that follows `box_sizing` like `width`.
+    /// For Text element, text is broken into lines in this width.
     pub max_width: Option<u32>,
     /// For Text element
     /// This property support multiline.
     pub text_overflow: TextOverflow,
+    /// For Img element.
+    /// Image box is exactly the size passed to `set_img` and image is fitted in it.
+    /// If this is not specified, box is shrunk to the image that fits in the size keeping its aspect ratio.
+    /// If this is specified, the source image is kept in memory in full resolution until it is painted.
+    pub object_fit: Option<ObjectFit>,
+    /// For Img element. This is used with `object_fit`.
+    pub object_position: ObjectPosition,
     pub position: Position,
     pub top: Option<i32>,
     pub right: Option<i32>,
     pub bottom: Option<i32>,
     pub left: Option<i32>,
-    /// For Img element
+    /// For absolute element, position is resolved in the whole window instead of inside `padding` of the window.
+    pub ignore_window_padding: bool,
+    /// Radius of the border box. Image is also clipped by this.
     pub border_radius: BorderRadius,
+    /// Color painted behind the padding box and border of the element.
+    pub background_color: Option<Rgba>,
+    /// Border is painted when this is greater than `0`.
+    pub border_width: u32,
+    pub border_color: Rgba,
+    pub border_style: BorderStyle,
+    pub box_shadow: Option<BoxShadow>,
+    /// Stacking order among elements in the same window or container.
+    /// Element with greater value is painted over, and elements with the same value are painted in the order they were set.
+    pub z_index: i32,
+    /// Override `align_items` of the window for this element.
+    pub align_self: Option<AlignItems>,
+    /// Ratio of remaining space in main axis that this element grows into.
+    pub flex_grow: f32,
+    /// Ratio that this element shrinks when items overflow main axis.
+    /// Unlike CSS, default is `0` so that elements keep their size.
+    pub flex_shrink: f32,
+    /// Initial size in main axis before growing or shrinking. Size of the content is used by default.
+    /// Text is broken into lines in the flexed width when the window is row direction.
+    pub flex_basis: Option<u32>,
 }
 
 impl Default for Style {
     fn default() -> Self {
         Style {
             margin: Margin::default(),
+            padding: Padding::default(),
+            width: None,
+            height: None,
+            min_width: None,
+            min_height: None,
+            box_sizing: BoxSizing::ContentBox,
             line_height: 1.5,
             font_size: 30.,
             letter_spacing: 0,
@@ -215,18 +494,112 @@ impl Default for Style {
             word_break: WordBreak::Normal,
             white_space: WhiteSpace::Normal,
             color: Rgba([0, 0, 0, 255]),
+            font_family: vec![],
+            font_weight: 400,
+            font_style: FontStyle::Normal,
             text_align: TextAlign::Start,
             max_height: None,
             max_width: None,
             text_overflow: TextOverflow::Clip,
+            object_fit: None,
+            object_position: ObjectPosition::default(),
             position: Position::Static,
             top: None,
             right: None,
             bottom: None,
             left: None,
+            ignore_window_padding: false,
             border_radius: BorderRadius::default(),
+            background_color: None,
+            border_width: 0,
+            border_color: Rgba([0, 0, 0, 255]),
+            border_style: BorderStyle::Solid,
+            box_shadow: None,
+            z_index: 0,
+            align_self: None,
+            flex_grow: 0.,
+            flex_shrink: 0.,
+            flex_basis: None,
+        }
+    }
+}
+
+impl Style {
+    // Padding and border around the content.
+    pub(crate) fn inset(&self) -> Padding {
+        let Padding(top, right, bottom, left) = self.padding;
+        let border = self.border_width;
+        Padding(top + border, right + border, bottom + border, left + border)
+    }
+
+    // Size of padding and border in horizontal and vertical.
+    pub(crate) fn inset_size(&self) -> (u32, u32) {
+        let Padding(top, right, bottom, left) = self.inset();
+        (left + right, top + bottom)
+    }
+
+    // Margin, border and padding around the content.
+    pub(crate) fn spacing(&self) -> Margin {
+        let Margin(margin_top, margin_right, margin_bottom, margin_left) = self.margin;
+        let Padding(top, right, bottom, left) = self.inset();
+        Margin(
+            margin_top + top as i32,
+            margin_right + right as i32,
+            margin_bottom + bottom as i32,
+            margin_left + left as i32,
+        )
+    }
+
+    // Convert size specified with `box_sizing` to size of the content box.
+    pub(crate) fn content_box(&self, size: u32, inset: u32) -> u32 {
+        match self.box_sizing {
+            BoxSizing::ContentBox => size,
+            BoxSizing::BorderBox => size.saturating_sub(inset),
+        }
+    }
+
+    // Convert size specified with `box_sizing` to size of the border box.
+    pub(crate) fn border_box(&self, size: u32, inset: u32) -> u32 {
+        match self.box_sizing {
+            BoxSizing::ContentBox => size + inset,
+            BoxSizing::BorderBox => size.max(inset),
         }
     }
+
+    // Resolve width of the content box from width of the content.
+    pub(crate) fn content_width(&self, width: u32) -> u32 {
+        let (inset, _) = self.inset_size();
+        let width = self.width.map_or(width, |w| self.content_box(w, inset));
+        let width = match self.max_width {
+            Some(max) => width.min(self.content_box(max, inset)),
+            None => width,
+        };
+        match self.min_width {
+            Some(min) => width.max(self.content_box(min, inset)),
+            None => width,
+        }
+    }
+
+    // Resolve height of the content box from height of the content.
+    pub(crate) fn content_height(&self, height: u32) -> u32 {
+        let (_, inset) = self.inset_size();
+        let height = self.height.map_or(height, |h| self.content_box(h, inset));
+        let height = match self.max_height {
+            Some(max) => height.min(self.content_box(max, inset)),
+            None => height,
+        };
+        match self.min_height {
+            Some(min) => height.max(self.content_box(min, inset)),
+            None => height,
+        }
+    }
+
+    // Height that text is truncated to.
+    pub(crate) fn content_max_height(&self) -> Option<u32> {
+        let (_, inset) = self.inset_size();
+        let max_height = [self.height, self.max_height].into_iter().flatten().min()?;
+        Some(self.content_box(max_height, inset))
+    }
 }
 
 pub enum LogicalFlexRowPosition {
@@ -238,30 +611,154 @@ pub enum LogicalFlexRowPosition {
 /// Window is act like flexbox. And default direction is `column`.
 /// You can adjust position with `align_item` and `justify_content`.
 /// You must pass `background_image` or `background_color` for constructing surface.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WindowStyle {
-    pub height: u32,
-    pub width: u32,
+    /// Height of the window. Window is sized by its content by default.
+    pub height: Option<u32>,
+    /// Width of the window. Window is sized by its content by default.
+    pub width: Option<u32>,
+    /// Bounds of width when the window is sized by its content.
+    /// Text is wrapped in `max_width`.
+    pub min_width: Option<u32>,
+    pub max_width: Option<u32>,
+    /// Bounds of height when the window is sized by its content.
+    pub min_height: Option<u32>,
+    pub max_height: Option<u32>,
     pub background_color: Option<Rgba>,
     pub align_items: AlignItems,
     pub justify_content: JustifyContent,
     /// This controls the direction in which the children of a node are laid out.
     pub flex_direction: FlexDirection,
+    /// Wrap items onto new lines or columns when they overflow the window.
+    pub flex_wrap: FlexWrap,
+    /// This is used when `flex_wrap` is not `NoWrap`.
+    /// Extra space is shared by lines with `Stretch`, and items are aligned in each line with `align_items`.
+    pub align_content: AlignContent,
+    /// Space between items and between lines.
+    pub gap: u32,
+    /// Space between rows. This overrides `gap`.
+    pub row_gap: Option<u32>,
+    /// Space between columns. This overrides `gap`.
+    pub column_gap: Option<u32>,
+    /// With `Hidden`, children of container are clipped by its padding box that follows `border_radius`.
+    /// Window is always clipped by the image.
+    pub overflow: Overflow,
+    /// Space inside the window that elements are kept away from, like safe area.
+    /// Absolute elements are also placed inside this unless `ignore_window_padding` is `true`.
+    pub padding: Padding,
 }
 
 impl WindowStyle {
+    // Size of padding in horizontal and vertical.
+    pub(crate) fn padding_size(&self) -> (u32, u32) {
+        let Padding(top, right, bottom, left) = self.padding;
+        (left + right, top + bottom)
+    }
+
+    // Width that text with `style` is wrapped in by default.
+    // Window that is sized by content wraps text in its max width.
+    // Text is not wrapped when the width is not bounded.
+    pub(crate) fn text_width(&self, style: &Style) -> u32 {
+        let window = WindowStyle {
+            width: self.width.or(self.max_width),
+            ..self.clone()
+        };
+        let width = match style.position {
+            Position::Absolute if style.ignore_window_padding => window.width,
+            _ => window.content_box().width,
+        };
+        width.unwrap_or(i32::MAX as u32 / 2)
+    }
+
+    pub(crate) fn is_sized_by_content(&self) -> bool {
+        self.width.is_none() || self.height.is_none()
+    }
+
+    // Width of the window that is resolved. This is `0` when the window is sized by content and not resolved yet.
+    pub(crate) fn resolved_width(&self) -> u32 {
+        self.width.unwrap_or(0)
+    }
+
+    // Height of the window that is resolved. This is `0` when the window is sized by content and not resolved yet.
+    pub(crate) fn resolved_height(&self) -> u32 {
+        self.height.unwrap_or(0)
+    }
+
+    // Return window that has the size that is not specified replaced by the content size in the bounds.
+    pub(crate) fn fit_content(&self, content_width: u32, content_height: u32) -> WindowStyle {
+        let fit = |size: Option<u32>, content: u32, min: Option<u32>, max: Option<u32>| {
+            size.or_else(|| {
+                let content = max.map_or(content, |max| content.min(max));
+                Some(min.map_or(content, |min| content.max(min)))
+            })
+        };
+        WindowStyle {
+            width: fit(self.width, content_width, self.min_width, self.max_width),
+            height: fit(
+                self.height,
+                content_height,
+                self.min_height,
+                self.max_height,
+            ),
+            ..self.clone()
+        }
+    }
+
+    // Return window that is reduced to the area inside padding.
+    pub(crate) fn content_box(&self) -> WindowStyle {
+        let (padding_width, padding_height) = self.padding_size();
+        WindowStyle {
+            width: self.width.map(|width| width.saturating_sub(padding_width)),
+            height: self
+                .height
+                .map(|height| height.saturating_sub(padding_height)),
+            padding: Padding::default(),
+            ..self.clone()
+        }
+    }
+
+    // Space between items in main axis.
+    pub(crate) fn main_gap(&self) -> u32 {
+        if self.flex_direction.is_column() {
+            self.row_gap.unwrap_or(self.gap)
+        } else {
+            self.column_gap.unwrap_or(self.gap)
+        }
+    }
+
+    // Space between lines in cross axis.
+    pub(crate) fn cross_gap(&self) -> u32 {
+        if self.flex_direction.is_column() {
+            self.column_gap.unwrap_or(self.gap)
+        } else {
+            self.row_gap.unwrap_or(self.gap)
+        }
+    }
+
     pub fn logical_flex_row_position(&self) -> LogicalFlexRowPosition {
         match &self.flex_direction {
-            FlexDirection::Column => match self.align_items {
+            FlexDirection::Column | FlexDirection::ColumnReverse => match self.align_items {
                 AlignItems::Start => LogicalFlexRowPosition::Start,
                 AlignItems::Center => LogicalFlexRowPosition::Center,
                 AlignItems::End => LogicalFlexRowPosition::End,
             },
             FlexDirection::Row => match self.justify_content {
-                JustifyContent::Start => LogicalFlexRowPosition::Start,
+                JustifyContent::Start
+                | JustifyContent::SpaceBetween
+                | JustifyContent::SpaceAround
+                | JustifyContent::SpaceEvenly => LogicalFlexRowPosition::Start,
                 JustifyContent::Center => LogicalFlexRowPosition::Center,
                 JustifyContent::End => LogicalFlexRowPosition::End,
             },
+            // Items are justified from the right.
+            FlexDirection::RowReverse => match self.justify_content {
+                JustifyContent::Start
+                | JustifyContent::SpaceBetween
+                | JustifyContent::SpaceAround
+                | JustifyContent::SpaceEvenly => LogicalFlexRowPosition::End,
+                JustifyContent::Center => LogicalFlexRowPosition::Center,
+                JustifyContent::End => LogicalFlexRowPosition::Start,
+            },
         }
     }
 }
@@ -269,12 +766,23 @@ impl WindowStyle {
 impl Default for WindowStyle {
     fn default() -> Self {
         WindowStyle {
-            height: 0,
-            width: 0,
+            height: None,
+            width: None,
+            min_width: None,
+            max_width: None,
+            min_height: None,
+            max_height: None,
             background_color: None,
             align_items: AlignItems::Start,
             justify_content: JustifyContent::Start,
             flex_direction: FlexDirection::Column,
+            flex_wrap: FlexWrap::NoWrap,
+            align_content: AlignContent::Stretch,
+            gap: 0,
+            row_gap: None,
+            column_gap: None,
+            overflow: Overflow::Visible,
+            padding: Padding::default(),
         }
     }
 }
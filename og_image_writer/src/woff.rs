@@ -0,0 +1,1040 @@
+// Decode WOFF and WOFF2 web font containers into sfnt (TrueType/OpenType) data.
+// See https://www.w3.org/TR/WOFF/ and https://www.w3.org/TR/WOFF2/
+use crate::Error;
+use brotli_decompressor::Decompressor;
+use miniz_oxide::inflate::core::inflate_flags::TINFL_FLAG_PARSE_ZLIB_HEADER;
+use miniz_oxide::inflate::core::{decompress, DecompressorOxide, TINFL_LZ_DICT_SIZE};
+use miniz_oxide::inflate::TINFLStatus;
+use std::io::Read;
+
+const WOFF_SIGNATURE: u32 = u32::from_be_bytes(*b"wOFF");
+const WOFF2_SIGNATURE: u32 = u32::from_be_bytes(*b"wOF2");
+const TTC_TAG: u32 = u32::from_be_bytes(*b"ttcf");
+const GLYF_TAG: u32 = u32::from_be_bytes(*b"glyf");
+const LOCA_TAG: u32 = u32::from_be_bytes(*b"loca");
+const HMTX_TAG: u32 = u32::from_be_bytes(*b"hmtx");
+const HHEA_TAG: u32 = u32::from_be_bytes(*b"hhea");
+const MAXP_TAG: u32 = u32::from_be_bytes(*b"maxp");
+
+const WOFF_HEADER_SIZE: usize = 44;
+const WOFF_TABLE_ENTRY_SIZE: usize = 20;
+
+// Tags that can be referred by index in WOFF2 table directory.
+const WOFF2_KNOWN_TAGS: [&[u8; 4]; 63] = [
+    b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"cvt ", b"fpgm",
+    b"glyf", b"loca", b"prep", b"CFF ", b"VORG", b"EBDT", b"EBLC", b"gasp", b"hdmx", b"kern",
+    b"LTSH", b"PCLT", b"VDMX", b"vhea", b"vmtx", b"BASE", b"GDEF", b"GPOS", b"GSUB", b"EBSC",
+    b"JSTF", b"MATH", b"CBDT", b"CBLC", b"COLR", b"CPAL", b"SVG ", b"sbix", b"acnt", b"avar",
+    b"bdat", b"bloc", b"bsln", b"cvar", b"fdsc", b"feat", b"fmtx", b"fvar", b"gvar", b"hsty",
+    b"just", b"lcar", b"mort", b"morx", b"opbd", b"prop", b"trak", b"Zapf", b"Silf", b"Glat",
+    b"Gloc", b"Feat", b"Sill",
+];
+
+// Flags for simple glyph in glyf table.
+const ON_CURVE_POINT: u8 = 0x01;
+const X_SHORT_VECTOR: u8 = 0x02;
+const Y_SHORT_VECTOR: u8 = 0x04;
+const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+const OVERLAP_SIMPLE: u8 = 0x40;
+
+// Flags for composite glyph in glyf table.
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+
+pub(super) fn is_woff(data: &[u8]) -> bool {
+    matches!(
+        Reader::new(data).u32(),
+        Some(WOFF_SIGNATURE) | Some(WOFF2_SIGNATURE)
+    )
+}
+
+/// Return sfnt data if `data` is WOFF or WOFF2. Otherwise `data` is returned as it is.
+pub(super) fn decode(data: Vec<u8>) -> Result<Vec<u8>, Error> {
+    let signature = Reader::new(&data).u32();
+    let decoded = match signature {
+        Some(WOFF_SIGNATURE) => decode_woff(&data),
+        Some(WOFF2_SIGNATURE) => decode_woff2(&data),
+        _ => return Ok(data),
+    };
+    decoded.ok_or(Error::InvalidFontBytes)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let bytes = self.data.get(self.pos..end)?;
+        self.pos = end;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn i16(&mut self) -> Option<i16> {
+        self.u16().map(|v| v as i16)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    // 255UInt16 data type in WOFF2.
+    fn u255_16(&mut self) -> Option<u16> {
+        match self.u8()? {
+            253 => self.u16(),
+            254 => Some(self.u8()? as u16 + 253 * 2),
+            255 => Some(self.u8()? as u16 + 253),
+            code => Some(code as u16),
+        }
+    }
+
+    // UIntBase128 data type in WOFF2.
+    fn base128(&mut self) -> Option<u32> {
+        let mut value: u32 = 0;
+        for i in 0..5 {
+            let byte = self.u8()?;
+            // Leading zeros are not allowed.
+            if i == 0 && byte == 0x80 {
+                return None;
+            }
+            if value & 0xFE00_0000 != 0 {
+                return None;
+            }
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+fn push_u16(buf: &mut Vec<u8>, v: u16) {
+    buf.extend_from_slice(&v.to_be_bytes());
+}
+
+fn push_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_be_bytes());
+}
+
+fn pad4(buf: &mut Vec<u8>) {
+    while !buf.len().is_multiple_of(4) {
+        buf.push(0);
+    }
+}
+
+struct Table {
+    tag: u32,
+    data: Vec<u8>,
+}
+
+// Font in sfnt file. `tables` is index for tables shared in the file.
+struct SfntFont {
+    flavor: u32,
+    tables: Vec<usize>,
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+// Write sfnt file. When `collection_version` is specified, the file is written as font collection.
+fn write_sfnt(fonts: &[SfntFont], tables: &[Table], collection_version: Option<u32>) -> Vec<u8> {
+    let header_size = match collection_version {
+        Some(version) => {
+            let dsig_size = if version >= 0x0002_0000 { 12 } else { 0 };
+            12 + 4 * fonts.len() + dsig_size
+        }
+        None => 0,
+    };
+    let directory_offsets: Vec<usize> = fonts
+        .iter()
+        .scan(header_size, |offset, font| {
+            let current = *offset;
+            *offset += 12 + 16 * font.tables.len();
+            Some(current)
+        })
+        .collect();
+
+    let mut table_offsets = Vec::with_capacity(tables.len());
+    let mut offset = header_size
+        + fonts
+            .iter()
+            .map(|f| 12 + 16 * f.tables.len())
+            .sum::<usize>();
+    for table in tables {
+        table_offsets.push(offset);
+        offset += (table.data.len() + 3) & !3;
+    }
+
+    let mut buf = Vec::with_capacity(offset);
+    if let Some(version) = collection_version {
+        push_u32(&mut buf, TTC_TAG);
+        push_u32(&mut buf, version);
+        push_u32(&mut buf, fonts.len() as u32);
+        for offset in &directory_offsets {
+            push_u32(&mut buf, *offset as u32);
+        }
+        if version >= 0x0002_0000 {
+            // No digital signature.
+            buf.extend_from_slice(&[0; 12]);
+        }
+    }
+
+    for font in fonts {
+        let num_tables = font.tables.len() as u16;
+        let entry_selector = if num_tables == 0 {
+            0
+        } else {
+            15 - num_tables.leading_zeros() as u16
+        };
+        let search_range = (1u16 << entry_selector).wrapping_mul(16);
+        push_u32(&mut buf, font.flavor);
+        push_u16(&mut buf, num_tables);
+        push_u16(&mut buf, search_range);
+        push_u16(&mut buf, entry_selector);
+        push_u16(
+            &mut buf,
+            num_tables.wrapping_mul(16).wrapping_sub(search_range),
+        );
+
+        // Table records must be sorted by tag.
+        let mut indices = font.tables.clone();
+        indices.sort_by_key(|i| tables[*i].tag);
+        for i in indices {
+            let table = &tables[i];
+            push_u32(&mut buf, table.tag);
+            push_u32(&mut buf, checksum(&table.data));
+            push_u32(&mut buf, table_offsets[i] as u32);
+            push_u32(&mut buf, table.data.len() as u32);
+        }
+    }
+
+    for table in tables {
+        buf.extend_from_slice(&table.data);
+        pad4(&mut buf);
+    }
+
+    buf
+}
+
+// Decompress zlib data that is declared to be `limit` bytes.
+// Output is grown only as it is decompressed, and data that is longer than `limit` is rejected.
+fn inflate_zlib(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut decompressor = DecompressorOxide::new();
+    // Decompressor writes output in the dictionary that wraps around.
+    let mut dict = vec![0; TINFL_LZ_DICT_SIZE];
+    let mut dict_ofs = 0;
+    let mut in_pos = 0;
+    let mut out = vec![];
+    loop {
+        let (status, in_consumed, out_consumed) = decompress(
+            &mut decompressor,
+            &data[in_pos..],
+            &mut dict,
+            dict_ofs,
+            TINFL_FLAG_PARSE_ZLIB_HEADER,
+        );
+        in_pos += in_consumed;
+        if out.len() + out_consumed > limit {
+            return None;
+        }
+        out.extend_from_slice(&dict[dict_ofs..dict_ofs + out_consumed]);
+        dict_ofs = (dict_ofs + out_consumed) & (TINFL_LZ_DICT_SIZE - 1);
+        match status {
+            TINFLStatus::Done => return Some(out),
+            TINFLStatus::HasMoreOutput => {}
+            _ => return None,
+        }
+    }
+}
+
+fn decode_woff(data: &[u8]) -> Option<Vec<u8>> {
+    let mut header = Reader::new(data);
+    header.u32()?;
+    let flavor = header.u32()?;
+    header.u32()?;
+    let num_tables = header.u16()?;
+
+    let mut tables = Vec::with_capacity(num_tables as usize);
+    for i in 0..num_tables as usize {
+        let mut entry = Reader::new(data);
+        entry.pos = WOFF_HEADER_SIZE + i * WOFF_TABLE_ENTRY_SIZE;
+        let tag = entry.u32()?;
+        let offset = entry.u32()? as usize;
+        let comp_length = entry.u32()? as usize;
+        let orig_length = entry.u32()? as usize;
+
+        let comp_data = data.get(offset..offset.checked_add(comp_length)?)?;
+        let table_data = if comp_length < orig_length {
+            inflate_zlib(comp_data, orig_length)?
+        } else if comp_length == orig_length {
+            comp_data.to_vec()
+        } else {
+            return None;
+        };
+        if table_data.len() != orig_length {
+            return None;
+        }
+
+        tables.push(Table {
+            tag,
+            data: table_data,
+        });
+    }
+
+    let font = SfntFont {
+        flavor,
+        tables: (0..tables.len()).collect(),
+    };
+    Some(write_sfnt(&[font], &tables, None))
+}
+
+struct Woff2TableEntry {
+    tag: u32,
+    is_transformed: bool,
+    // Length of table data in decompressed stream.
+    transform_length: usize,
+}
+
+fn decode_woff2(data: &[u8]) -> Option<Vec<u8>> {
+    let mut r = Reader::new(data);
+    r.u32()?;
+    let flavor = r.u32()?;
+    r.u32()?;
+    let num_tables = r.u16()?;
+    r.u16()?;
+    r.u32()?;
+    let total_compressed_size = r.u32()? as usize;
+    // majorVersion, minorVersion, metaOffset, metaLength, metaOrigLength, privOffset and privLength
+    r.bytes(24)?;
+
+    let mut entries = Vec::with_capacity(num_tables as usize);
+    for _ in 0..num_tables {
+        let flags = r.u8()?;
+        let tag = match flags & 0x3F {
+            0x3F => r.u32()?,
+            i => u32::from_be_bytes(*WOFF2_KNOWN_TAGS[i as usize]),
+        };
+        let orig_length = r.base128()? as usize;
+        let transform_version = flags >> 6;
+        // For glyf and loca table, version 3 is null transform.
+        // For other tables, version 0 is null transform.
+        let is_transformed = if tag == GLYF_TAG || tag == LOCA_TAG {
+            transform_version != 3
+        } else {
+            transform_version != 0
+        };
+        let transform_length = if is_transformed {
+            r.base128()? as usize
+        } else {
+            orig_length
+        };
+        entries.push(Woff2TableEntry {
+            tag,
+            is_transformed,
+            transform_length,
+        });
+    }
+
+    let (fonts, collection_version) = if flavor == TTC_TAG {
+        let version = r.u32()?;
+        let num_fonts = r.u255_16()?;
+        let mut fonts = Vec::with_capacity(num_fonts as usize);
+        for _ in 0..num_fonts {
+            let num_tables = r.u255_16()?;
+            let flavor = r.u32()?;
+            let mut tables = Vec::with_capacity(num_tables as usize);
+            for _ in 0..num_tables {
+                let idx = r.u255_16()? as usize;
+                if idx >= entries.len() {
+                    return None;
+                }
+                tables.push(idx);
+            }
+            fonts.push(SfntFont { flavor, tables });
+        }
+        (fonts, Some(version))
+    } else {
+        let font = SfntFont {
+            flavor,
+            tables: (0..entries.len()).collect(),
+        };
+        (vec![font], None)
+    };
+
+    // Stream has transformed tables in order.
+    // It is read up to one byte over their total length, so stream that is longer is rejected without reading all of it.
+    let stream_length = entries.iter().try_fold(0usize, |total, entry| {
+        total.checked_add(entry.transform_length)
+    })?;
+    let compressed = r.bytes(total_compressed_size)?;
+    let mut stream = vec![];
+    Decompressor::new(compressed, 4096)
+        .take(stream_length as u64 + 1)
+        .read_to_end(&mut stream)
+        .ok()?;
+    if stream.len() != stream_length {
+        return None;
+    }
+
+    let mut stream_reader = Reader::new(&stream);
+    let mut table_data: Vec<Option<Vec<u8>>> = Vec::with_capacity(entries.len());
+    let mut transformed_data: Vec<&[u8]> = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let bytes = stream_reader.bytes(entry.transform_length)?;
+        if entry.is_transformed {
+            table_data.push(None);
+        } else {
+            table_data.push(Some(bytes.to_vec()));
+        }
+        transformed_data.push(bytes);
+    }
+
+    let find_table = |font: &SfntFont, tag: u32| -> Option<usize> {
+        font.tables.iter().copied().find(|i| entries[*i].tag == tag)
+    };
+
+    // Reconstruct glyf and loca tables first because hmtx table depends on glyf table.
+    let mut x_mins: Vec<Option<Vec<i16>>> = (0..entries.len()).map(|_| None).collect();
+    for font in &fonts {
+        let glyf = match find_table(font, GLYF_TAG) {
+            Some(glyf) if entries[glyf].is_transformed => glyf,
+            _ => continue,
+        };
+        if table_data[glyf].is_some() {
+            // Already reconstructed by other font in the collection.
+            continue;
+        }
+        let loca = find_table(font, LOCA_TAG)?;
+        let glyph_table = reconstruct_glyf(transformed_data[glyf])?;
+        table_data[glyf] = Some(glyph_table.glyf);
+        table_data[loca] = Some(glyph_table.loca);
+        x_mins[glyf] = Some(glyph_table.x_mins);
+    }
+
+    for font in &fonts {
+        let hmtx = match find_table(font, HMTX_TAG) {
+            Some(hmtx) if entries[hmtx].is_transformed => hmtx,
+            _ => continue,
+        };
+        if table_data[hmtx].is_some() {
+            continue;
+        }
+        let glyf = find_table(font, GLYF_TAG)?;
+        let hhea = table_data[find_table(font, HHEA_TAG)?].as_ref()?;
+        let maxp = table_data[find_table(font, MAXP_TAG)?].as_ref()?;
+
+        let mut hhea_reader = Reader::new(hhea);
+        hhea_reader.pos = 34;
+        let num_hmetrics = hhea_reader.u16()?;
+        let mut maxp_reader = Reader::new(maxp);
+        maxp_reader.pos = 4;
+        let num_glyphs = maxp_reader.u16()?;
+
+        table_data[hmtx] = Some(reconstruct_hmtx(
+            transformed_data[hmtx],
+            num_glyphs,
+            num_hmetrics,
+            x_mins[glyf].as_ref()?,
+        )?);
+    }
+
+    let tables = entries
+        .iter()
+        .zip(table_data)
+        .map(|(entry, data)| {
+            data.map(|data| Table {
+                tag: entry.tag,
+                data,
+            })
+        })
+        .collect::<Option<Vec<Table>>>()?;
+
+    Some(write_sfnt(&fonts, &tables, collection_version))
+}
+
+struct GlyphTable {
+    glyf: Vec<u8>,
+    loca: Vec<u8>,
+    x_mins: Vec<i16>,
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+    on_curve: bool,
+}
+
+// Decode point delta from triplet encoding.
+// Return (dx, dy, on_curve).
+fn decode_triplet(flag: u8, glyph_stream: &mut Reader) -> Option<(i32, i32, bool)> {
+    fn with_sign(flag: u8, base: i32) -> i32 {
+        if flag & 1 != 0 {
+            base
+        } else {
+            -base
+        }
+    }
+
+    let on_curve = flag & 0x80 == 0;
+    let flag = flag & 0x7F;
+    let (dx, dy) = if flag < 10 {
+        let b0 = glyph_stream.u8()? as i32;
+        (0, with_sign(flag, (((flag & 14) as i32) << 7) + b0))
+    } else if flag < 20 {
+        let b0 = glyph_stream.u8()? as i32;
+        (with_sign(flag, ((((flag - 10) & 14) as i32) << 7) + b0), 0)
+    } else if flag < 84 {
+        let b0 = (flag - 20) as i32;
+        let b1 = glyph_stream.u8()? as i32;
+        (
+            with_sign(flag, 1 + (b0 & 0x30) + (b1 >> 4)),
+            with_sign(flag >> 1, 1 + ((b0 & 0x0C) << 2) + (b1 & 0x0F)),
+        )
+    } else if flag < 120 {
+        let b0 = (flag - 84) as i32;
+        let b1 = glyph_stream.u8()? as i32;
+        let b2 = glyph_stream.u8()? as i32;
+        (
+            with_sign(flag, 1 + ((b0 / 12) << 8) + b1),
+            with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + b2),
+        )
+    } else if flag < 124 {
+        let b = glyph_stream.bytes(3)?;
+        let (b0, b1, b2) = (b[0] as i32, b[1] as i32, b[2] as i32);
+        (
+            with_sign(flag, (b0 << 4) + (b1 >> 4)),
+            with_sign(flag >> 1, ((b1 & 0x0F) << 8) + b2),
+        )
+    } else {
+        let b = glyph_stream.bytes(4)?;
+        (
+            with_sign(flag, ((b[0] as i32) << 8) + b[1] as i32),
+            with_sign(flag >> 1, ((b[2] as i32) << 8) + b[3] as i32),
+        )
+    };
+    Some((dx, dy, on_curve))
+}
+
+fn write_points(buf: &mut Vec<u8>, points: &[Point], has_overlap: bool) -> Option<()> {
+    let mut flags = Vec::with_capacity(points.len());
+    let mut xs = vec![];
+    let mut ys = vec![];
+    let (mut last_x, mut last_y) = (0, 0);
+    for (i, point) in points.iter().enumerate() {
+        let mut flag = if point.on_curve { ON_CURVE_POINT } else { 0 };
+        if has_overlap && i == 0 {
+            flag |= OVERLAP_SIMPLE;
+        }
+
+        let dx = point.x - last_x;
+        if dx == 0 {
+            flag |= X_IS_SAME_OR_POSITIVE;
+        } else if dx > -256 && dx < 256 {
+            flag |= X_SHORT_VECTOR;
+            if dx > 0 {
+                flag |= X_IS_SAME_OR_POSITIVE;
+            }
+            xs.push(dx.unsigned_abs() as u8);
+        } else {
+            push_u16(&mut xs, i16::try_from(dx).ok()? as u16);
+        }
+
+        let dy = point.y - last_y;
+        if dy == 0 {
+            flag |= Y_IS_SAME_OR_POSITIVE;
+        } else if dy > -256 && dy < 256 {
+            flag |= Y_SHORT_VECTOR;
+            if dy > 0 {
+                flag |= Y_IS_SAME_OR_POSITIVE;
+            }
+            ys.push(dy.unsigned_abs() as u8);
+        } else {
+            push_u16(&mut ys, i16::try_from(dy).ok()? as u16);
+        }
+
+        flags.push(flag);
+        last_x = point.x;
+        last_y = point.y;
+    }
+
+    buf.extend_from_slice(&flags);
+    buf.extend_from_slice(&xs);
+    buf.extend_from_slice(&ys);
+    Some(())
+}
+
+// Reconstruct glyf and loca tables from transformed glyf table.
+fn reconstruct_glyf(data: &[u8]) -> Option<GlyphTable> {
+    let mut header = Reader::new(data);
+    header.u16()?;
+    let option_flags = header.u16()?;
+    let num_glyphs = header.u16()? as usize;
+    let index_format = header.u16()?;
+    let mut stream_sizes = [0usize; 7];
+    for size in stream_sizes.iter_mut() {
+        *size = header.u32()? as usize;
+    }
+
+    let mut streams = Vec::with_capacity(stream_sizes.len());
+    for size in stream_sizes {
+        streams.push(header.bytes(size)?);
+    }
+    let mut n_contour_stream = Reader::new(streams[0]);
+    let mut n_points_stream = Reader::new(streams[1]);
+    let mut flag_stream = Reader::new(streams[2]);
+    let mut glyph_stream = Reader::new(streams[3]);
+    let mut composite_stream = Reader::new(streams[4]);
+    let mut bbox_stream = Reader::new(streams[5]);
+    let mut instruction_stream = Reader::new(streams[6]);
+
+    let overlap_bitmap = if option_flags & 1 != 0 {
+        Some(header.bytes(num_glyphs.div_ceil(8))?)
+    } else {
+        None
+    };
+    let bbox_bitmap = bbox_stream.bytes(4 * num_glyphs.div_ceil(32))?;
+    let has_bit = |bitmap: &[u8], i: usize| bitmap[i >> 3] & (0x80 >> (i & 7)) != 0;
+
+    let mut glyf = vec![];
+    let mut offsets = Vec::with_capacity(num_glyphs + 1);
+    let mut x_mins = Vec::with_capacity(num_glyphs);
+    for i in 0..num_glyphs {
+        offsets.push(glyf.len());
+        let n_contours = n_contour_stream.i16()?;
+        let has_bbox = has_bit(bbox_bitmap, i);
+
+        match n_contours {
+            0 => {
+                // Empty glyph must not have bounding box.
+                if has_bbox {
+                    return None;
+                }
+                x_mins.push(0);
+            }
+            -1 => {
+                // Composite glyph must have explicit bounding box.
+                if !has_bbox {
+                    return None;
+                }
+                push_u16(&mut glyf, n_contours as u16);
+                let bbox = bbox_stream.bytes(8)?;
+                x_mins.push(i16::from_be_bytes([bbox[0], bbox[1]]));
+                glyf.extend_from_slice(bbox);
+
+                let mut has_instructions = false;
+                loop {
+                    let flags = composite_stream.u16()?;
+                    push_u16(&mut glyf, flags);
+                    let mut size = 2 + if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                        4
+                    } else {
+                        2
+                    };
+                    if flags & WE_HAVE_A_SCALE != 0 {
+                        size += 2;
+                    } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                        size += 4;
+                    } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                        size += 8;
+                    }
+                    glyf.extend_from_slice(composite_stream.bytes(size)?);
+                    has_instructions |= flags & WE_HAVE_INSTRUCTIONS != 0;
+                    if flags & MORE_COMPONENTS == 0 {
+                        break;
+                    }
+                }
+
+                if has_instructions {
+                    let len = glyph_stream.u255_16()?;
+                    push_u16(&mut glyf, len);
+                    glyf.extend_from_slice(instruction_stream.bytes(len as usize)?);
+                }
+            }
+            n if n > 0 => {
+                let mut end_points = Vec::with_capacity(n as usize);
+                let mut total_points = 0usize;
+                for _ in 0..n {
+                    total_points += n_points_stream.u255_16()? as usize;
+                    if total_points == 0 {
+                        return None;
+                    }
+                    end_points.push(u16::try_from(total_points - 1).ok()?);
+                }
+
+                let mut points = Vec::with_capacity(total_points);
+                let (mut x, mut y) = (0, 0);
+                for _ in 0..total_points {
+                    let flag = flag_stream.u8()?;
+                    let (dx, dy, on_curve) = decode_triplet(flag, &mut glyph_stream)?;
+                    x += dx;
+                    y += dy;
+                    points.push(Point { x, y, on_curve });
+                }
+                let instruction_len = glyph_stream.u255_16()?;
+
+                let bbox = if has_bbox {
+                    let b = bbox_stream.bytes(8)?;
+                    [
+                        i16::from_be_bytes([b[0], b[1]]),
+                        i16::from_be_bytes([b[2], b[3]]),
+                        i16::from_be_bytes([b[4], b[5]]),
+                        i16::from_be_bytes([b[6], b[7]]),
+                    ]
+                } else {
+                    let x_min = points.iter().map(|p| p.x).min()?;
+                    let y_min = points.iter().map(|p| p.y).min()?;
+                    let x_max = points.iter().map(|p| p.x).max()?;
+                    let y_max = points.iter().map(|p| p.y).max()?;
+                    [
+                        i16::try_from(x_min).ok()?,
+                        i16::try_from(y_min).ok()?,
+                        i16::try_from(x_max).ok()?,
+                        i16::try_from(y_max).ok()?,
+                    ]
+                };
+                x_mins.push(bbox[0]);
+
+                push_u16(&mut glyf, n_contours as u16);
+                for v in bbox {
+                    push_u16(&mut glyf, v as u16);
+                }
+                for end_point in end_points {
+                    push_u16(&mut glyf, end_point);
+                }
+                push_u16(&mut glyf, instruction_len);
+                glyf.extend_from_slice(instruction_stream.bytes(instruction_len as usize)?);
+
+                let has_overlap = overlap_bitmap
+                    .map(|bitmap| has_bit(bitmap, i))
+                    .unwrap_or(false);
+                write_points(&mut glyf, &points, has_overlap)?;
+            }
+            _ => return None,
+        }
+
+        pad4(&mut glyf);
+    }
+    offsets.push(glyf.len());
+
+    let mut loca = vec![];
+    for offset in offsets {
+        if index_format == 0 {
+            push_u16(&mut loca, u16::try_from(offset / 2).ok()?);
+        } else {
+            push_u32(&mut loca, u32::try_from(offset).ok()?);
+        }
+    }
+
+    Some(GlyphTable { glyf, loca, x_mins })
+}
+
+// Reconstruct hmtx table from transformed hmtx table.
+// Omitted left side bearings are same as xMin of glyph bounding box.
+fn reconstruct_hmtx(
+    data: &[u8],
+    num_glyphs: u16,
+    num_hmetrics: u16,
+    x_mins: &[i16],
+) -> Option<Vec<u8>> {
+    let mut r = Reader::new(data);
+    let flags = r.u8()?;
+    let has_proportional_lsb = flags & 1 == 0;
+    let has_monospace_lsb = flags & 2 == 0;
+    if num_hmetrics > num_glyphs || x_mins.len() < num_glyphs as usize {
+        return None;
+    }
+
+    let mut advance_widths = Vec::with_capacity(num_hmetrics as usize);
+    for _ in 0..num_hmetrics {
+        advance_widths.push(r.u16()?);
+    }
+
+    let mut lsbs = Vec::with_capacity(num_glyphs as usize);
+    for (i, x_min) in x_mins.iter().take(num_glyphs as usize).enumerate() {
+        let has_lsb = if i < num_hmetrics as usize {
+            has_proportional_lsb
+        } else {
+            has_monospace_lsb
+        };
+        lsbs.push(if has_lsb { r.i16()? } else { *x_min });
+    }
+
+    let mut hmtx = Vec::with_capacity(num_hmetrics as usize * 2 + num_glyphs as usize * 2);
+    for (i, lsb) in lsbs.into_iter().enumerate() {
+        if let Some(advance_width) = advance_widths.get(i) {
+            push_u16(&mut hmtx, *advance_width);
+        }
+        push_u16(&mut hmtx, lsb as u16);
+    }
+    Some(hmtx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miniz_oxide::deflate::compress_to_vec_zlib;
+    use std::io::Write;
+
+    const FONT: &[u8] = include_bytes!("../../fonts/Roboto-Light.ttf");
+
+    // Return (flavor, tables) in sfnt.
+    fn read_sfnt(data: &[u8]) -> (u32, Vec<Table>) {
+        let mut r = Reader::new(data);
+        let flavor = r.u32().unwrap();
+        let num_tables = r.u16().unwrap();
+        let tables = (0..num_tables as usize)
+            .map(|i| {
+                let mut r = Reader::new(data);
+                r.pos = 12 + i * 16;
+                let tag = r.u32().unwrap();
+                r.u32().unwrap();
+                let offset = r.u32().unwrap() as usize;
+                let length = r.u32().unwrap() as usize;
+                Table {
+                    tag,
+                    data: data[offset..offset + length].to_vec(),
+                }
+            })
+            .collect();
+        (flavor, tables)
+    }
+
+    fn encode_woff(data: &[u8]) -> Vec<u8> {
+        let (flavor, tables) = read_sfnt(data);
+        let mut directory = vec![];
+        let mut body = vec![];
+        let mut offset = WOFF_HEADER_SIZE + WOFF_TABLE_ENTRY_SIZE * tables.len();
+        for table in &tables {
+            let compressed = compress_to_vec_zlib(&table.data, 6);
+            let comp_data = if compressed.len() < table.data.len() {
+                compressed
+            } else {
+                table.data.clone()
+            };
+            push_u32(&mut directory, table.tag);
+            push_u32(&mut directory, offset as u32);
+            push_u32(&mut directory, comp_data.len() as u32);
+            push_u32(&mut directory, table.data.len() as u32);
+            push_u32(&mut directory, checksum(&table.data));
+            body.extend_from_slice(&comp_data);
+            pad4(&mut body);
+            offset = WOFF_HEADER_SIZE + WOFF_TABLE_ENTRY_SIZE * tables.len() + body.len();
+        }
+
+        let mut woff = vec![];
+        push_u32(&mut woff, WOFF_SIGNATURE);
+        push_u32(&mut woff, flavor);
+        push_u32(
+            &mut woff,
+            (WOFF_HEADER_SIZE + directory.len() + body.len()) as u32,
+        );
+        push_u16(&mut woff, tables.len() as u16);
+        push_u16(&mut woff, 0);
+        push_u32(&mut woff, data.len() as u32);
+        woff.extend_from_slice(&[0; 24]);
+        woff.extend_from_slice(&directory);
+        woff.extend_from_slice(&body);
+        woff
+    }
+
+    fn push_base128(buf: &mut Vec<u8>, v: u32) {
+        let mut bytes = vec![(v & 0x7F) as u8];
+        let mut v = v >> 7;
+        while v > 0 {
+            bytes.push((v & 0x7F) as u8 | 0x80);
+            v >>= 7;
+        }
+        bytes.reverse();
+        buf.extend_from_slice(&bytes);
+    }
+
+    // Encode WOFF2 with null transform for all tables.
+    fn encode_woff2(data: &[u8]) -> Vec<u8> {
+        encode_woff2_with_trailing(data, &[])
+    }
+
+    // Encode WOFF2 that has `trailing` bytes after the tables in the compressed stream.
+    fn encode_woff2_with_trailing(data: &[u8], trailing: &[u8]) -> Vec<u8> {
+        let (flavor, tables) = read_sfnt(data);
+        let mut directory = vec![];
+        let mut stream = vec![];
+        for table in &tables {
+            let transform_version = if table.tag == GLYF_TAG || table.tag == LOCA_TAG {
+                3
+            } else {
+                0
+            };
+            directory.push((transform_version << 6) | 0x3F);
+            push_u32(&mut directory, table.tag);
+            push_base128(&mut directory, table.data.len() as u32);
+            stream.extend_from_slice(&table.data);
+        }
+        stream.extend_from_slice(trailing);
+
+        let mut compressed = vec![];
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+            writer.write_all(&stream).unwrap();
+        }
+
+        let mut woff2 = vec![];
+        push_u32(&mut woff2, WOFF2_SIGNATURE);
+        push_u32(&mut woff2, flavor);
+        push_u32(&mut woff2, 0);
+        push_u16(&mut woff2, tables.len() as u16);
+        push_u16(&mut woff2, 0);
+        push_u32(&mut woff2, data.len() as u32);
+        push_u32(&mut woff2, compressed.len() as u32);
+        woff2.extend_from_slice(&[0; 24]);
+        woff2.extend_from_slice(&directory);
+        woff2.extend_from_slice(&compressed);
+        woff2
+    }
+
+    fn assert_same_tables(expected: &[u8], actual: &[u8]) {
+        let (expected_flavor, mut expected_tables) = read_sfnt(expected);
+        let (actual_flavor, actual_tables) = read_sfnt(actual);
+        expected_tables.sort_by_key(|t| t.tag);
+
+        assert_eq!(expected_flavor, actual_flavor);
+        assert_eq!(expected_tables.len(), actual_tables.len());
+        for (expected, actual) in expected_tables.iter().zip(actual_tables.iter()) {
+            assert_eq!(expected.tag, actual.tag);
+            assert!(expected.data == actual.data);
+        }
+        assert!(ttf_parser::Face::parse(actual, 0).is_ok());
+    }
+
+    #[test]
+    fn test_decode_sfnt() {
+        let decoded = decode(FONT.to_vec()).unwrap();
+        assert!(decoded == FONT);
+    }
+
+    #[test]
+    fn test_decode_woff() {
+        let decoded = decode(encode_woff(FONT)).unwrap();
+        assert_same_tables(FONT, &decoded);
+    }
+
+    #[test]
+    fn test_decode_woff2() {
+        let decoded = decode(encode_woff2(FONT)).unwrap();
+        assert_same_tables(FONT, &decoded);
+    }
+
+    #[test]
+    fn test_decode_invalid_woff2() {
+        let mut woff2 = encode_woff2(FONT);
+        woff2.truncate(100);
+        assert!(decode(woff2).is_err());
+    }
+
+    #[test]
+    fn test_decode_woff_larger_than_declared() {
+        // Table is declared as 1000 bytes, but it is decompressed into 1MB.
+        let comp_data = compress_to_vec_zlib(&vec![0; 1 << 20], 6);
+        let mut woff = vec![];
+        push_u32(&mut woff, WOFF_SIGNATURE);
+        push_u32(&mut woff, 0x00010000);
+        push_u32(&mut woff, 0);
+        push_u16(&mut woff, 1);
+        push_u16(&mut woff, 0);
+        push_u32(&mut woff, 1000);
+        woff.extend_from_slice(&[0; 24]);
+        push_u32(&mut woff, u32::from_be_bytes(*b"name"));
+        push_u32(&mut woff, (WOFF_HEADER_SIZE + WOFF_TABLE_ENTRY_SIZE) as u32);
+        push_u32(&mut woff, comp_data.len() as u32);
+        push_u32(&mut woff, 1000);
+        push_u32(&mut woff, 0);
+        woff.extend_from_slice(&comp_data);
+        assert!(decode(woff).is_err());
+    }
+
+    #[test]
+    fn test_decode_woff2_larger_than_declared() {
+        let woff2 = encode_woff2_with_trailing(FONT, &vec![0; 1 << 20]);
+        assert!(decode(woff2).is_err());
+    }
+
+    #[test]
+    fn test_reconstruct_glyf() {
+        // Triangle (0, 0), (100, 0), (0, 100) and empty glyph.
+        let n_contour_stream = [0x00, 0x01, 0x00, 0x00];
+        let n_points_stream = [3];
+        let flag_stream = [1, 11, 86];
+        let glyph_stream = [0, 100, 99, 99, 0];
+        let bbox_stream = [0, 0, 0, 0];
+
+        let mut data = vec![];
+        push_u16(&mut data, 0);
+        push_u16(&mut data, 0);
+        push_u16(&mut data, 2);
+        push_u16(&mut data, 0);
+        let streams: [&[u8]; 7] = [
+            &n_contour_stream,
+            &n_points_stream,
+            &flag_stream,
+            &glyph_stream,
+            &[],
+            &bbox_stream,
+            &[],
+        ];
+        for stream in streams {
+            push_u32(&mut data, stream.len() as u32);
+        }
+        for stream in streams {
+            data.extend_from_slice(stream);
+        }
+
+        let glyph_table = reconstruct_glyf(&data).unwrap();
+
+        let mut expected = vec![];
+        // numberOfContours and bounding box
+        for v in [1u16, 0, 0, 100, 100] {
+            push_u16(&mut expected, v);
+        }
+        // endPtsOfContours and instructionLength
+        push_u16(&mut expected, 2);
+        push_u16(&mut expected, 0);
+        // flags
+        expected.extend_from_slice(&[
+            ON_CURVE_POINT | X_IS_SAME_OR_POSITIVE | Y_IS_SAME_OR_POSITIVE,
+            ON_CURVE_POINT | X_SHORT_VECTOR | X_IS_SAME_OR_POSITIVE | Y_IS_SAME_OR_POSITIVE,
+            ON_CURVE_POINT | X_SHORT_VECTOR | Y_SHORT_VECTOR | Y_IS_SAME_OR_POSITIVE,
+        ]);
+        // x and y coordinates
+        expected.extend_from_slice(&[100, 100, 100]);
+        pad4(&mut expected);
+
+        assert_eq!(glyph_table.glyf, expected);
+        assert_eq!(glyph_table.loca, [0, 0, 0, 10, 0, 10]);
+        assert_eq!(glyph_table.x_mins, [0, 0]);
+    }
+}
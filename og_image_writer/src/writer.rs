@@ -2,23 +2,38 @@ use crate::Error;
 use image::{ImageError, RgbaImage};
 
 use super::context::{Context, ImageOutputFormat};
-use super::element::{Element, Fragment, Img, Text};
+use super::element::{Container, Element, Fragment, Img, Text};
 use super::font::create_font;
 use super::font_context::{FontContext, FontIndexStore};
 use super::font_trait::Font;
 use super::glyph::Glyph;
-use super::img::ImageInputFormat;
-use super::layout::{SplitText, TextArea};
+use super::img::{fit, inner_radius, resize_exact, round, ImageInputFormat};
+use super::layout::{LayoutEngine, SplitText, TextArea};
+use super::layout_tree::LayoutTree;
+use super::measure::{measure_text, measure_textarea, TextMeasurement};
 use super::renderer::FontSetting;
-use super::style::{Style, WindowStyle};
+use super::shape::Shape;
+use super::style::{Overflow, Style, WindowStyle};
 use std::{cell::RefCell, ops::Range, path::Path, str};
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone)]
 pub(super) struct Content {
-    pub(super) height: u32,
-    pub(super) width: u32,
+    pub(super) height: i32,
+    pub(super) width: i32,
 }
 
+impl Content {
+    // Negative margins can make the content smaller than `0`, so it is clamped at `0`.
+    pub(super) fn height(&self) -> u32 {
+        self.height.max(0) as u32
+    }
+
+    pub(super) fn width(&self) -> u32 {
+        self.width.max(0) as u32
+    }
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct Tree(pub(super) Vec<Element>);
 
 /// This struct write text to image.
@@ -30,13 +45,15 @@ pub struct OGImageWriter {
     pub(super) window: WindowStyle,
     pub(super) content: Content,
     pub(super) font_context: FontContext,
-    pub(super) is_container: bool,
+    pub(super) debug: bool,
+    pub(super) layout_engine: LayoutEngine,
 }
 
 impl OGImageWriter {
     /// Set window style. Window act like CSS `flexbox`.
+    /// If width or height is `None`, the window is sized by its content, and the image is allocated when it is painted.
     pub fn new(window: WindowStyle) -> Result<Self, Error> {
-        let context = Context::new(window.width, window.height);
+        let context = Context::new(window.resolved_width(), window.resolved_height());
 
         let mut this = OGImageWriter {
             context,
@@ -44,7 +61,8 @@ impl OGImageWriter {
             window,
             content: Content::default(),
             font_context: FontContext::new(),
-            is_container: false,
+            debug: false,
+            layout_engine: LayoutEngine::default(),
         };
 
         this.process_background()?;
@@ -73,13 +91,14 @@ impl OGImageWriter {
             context,
             tree: OGImageWriter::create_tree(),
             window: WindowStyle {
-                width,
-                height,
+                width: Some(width),
+                height: Some(height),
                 ..window
             },
             content: Content::default(),
             font_context: FontContext::new(),
-            is_container: false,
+            debug: false,
+            layout_engine: LayoutEngine::default(),
         })
     }
 
@@ -87,12 +106,26 @@ impl OGImageWriter {
         Tree(Vec::with_capacity(2))
     }
 
+    /// Return width of the window. This is `0` until painted when the window is sized by its content.
     pub fn width(&self) -> u32 {
-        self.window.width
+        self.window.resolved_width()
     }
 
+    /// Return height of the window. This is `0` until painted when the window is sized by its content.
     pub fn height(&self) -> u32 {
-        self.window.height
+        self.window.resolved_height()
+    }
+
+    /// Set [FontContext](super::font_context::FontContext) that provides fallback fonts and font families.
+    /// FontContext is shared with the caller, so fonts pushed to it later are also available.
+    /// Texts that are already set keep using the FontContext that was set at that time.
+    pub fn set_font_context(&mut self, font_context: &FontContext) {
+        self.font_context = font_context.clone();
+    }
+
+    /// Return [FontContext](super::font_context::FontContext) used by this writer.
+    pub fn font_context(&self) -> &FontContext {
+        &self.font_context
     }
 
     /// Set text you want to write to image.
@@ -114,6 +147,39 @@ impl OGImageWriter {
         self.process_text(textarea, style, font)
     }
 
+    /// Measure text laid out in this window without setting it.
+    /// See [measure](super::measure) module.
+    pub fn measure_text(
+        &self,
+        text: &str,
+        style: &Style,
+        font: Option<Vec<u8>>,
+    ) -> Result<TextMeasurement, Error> {
+        measure_text(
+            text,
+            style,
+            font,
+            self.window.text_width(style),
+            &self.font_context,
+        )
+    }
+
+    /// Measure [TextArea](super::TextArea) laid out in this window without setting it.
+    pub fn measure_textarea(
+        &self,
+        textarea: &TextArea,
+        style: &Style,
+        font: Option<Vec<u8>>,
+    ) -> Result<TextMeasurement, Error> {
+        measure_textarea(
+            textarea,
+            style,
+            font,
+            self.window.text_width(style),
+            &self.font_context,
+        )
+    }
+
     /// Set [TextArea](super::TextArea) to image.
     pub fn set_textarea(
         &mut self,
@@ -151,15 +217,12 @@ impl OGImageWriter {
         self.process_img_with_data(data, width, height, format, style)
     }
 
-    /// Set generated image by [OGImageWriter](Self) on parent image
+    /// Set elements of [OGImageWriter](Self) as nested flex container.
+    /// The container is laid out with its own window style, and painted with this writer.
+    /// If width or height of the container window is `None`, it is sized by its content. Size of `Some(0)` is kept as `0`.
+    /// Elements of `writer` are moved to this writer.
     pub fn set_container(&mut self, writer: &mut OGImageWriter, style: Style) -> Result<(), Error> {
-        writer.is_container = true;
-
-        writer.paint()?;
-
-        self.process_container(writer, style)?;
-
-        Ok(())
+        self.process_container(writer, style)
     }
 
     /// Generate your image.
@@ -169,19 +232,41 @@ impl OGImageWriter {
         self.context.save(dest)
     }
 
+    /// Return the computed layout of elements that are set, without painting them.
+    /// Layout is not available after painting because painted elements are consumed.
+    pub fn layout(&self) -> LayoutTree {
+        let window = self.sized_window();
+        let mut tree = self.tree.clone();
+        self.process_tree(&window, &mut tree);
+        LayoutTree::new(&window, &tree)
+    }
+
+    /// Draw computed layout over painted image for debugging.
+    /// Margin boxes, border boxes, lines of text, baselines and the main axis of the window are drawn in different colors.
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    /// Set [LayoutEngine](super::LayoutEngine) that computes layout of elements.
+    pub fn set_layout_engine(&mut self, layout_engine: LayoutEngine) {
+        self.layout_engine = layout_engine;
+    }
+
     pub fn paint(&mut self) -> Result<(), Error> {
+        self.process_size()?;
         self.process();
 
-        while let Some(elm) = self.tree.0.pop() {
-            match elm {
-                Element::Img(Some(img)) => self.paint_img(img)?,
-                Element::Text(Some(text)) => self.paint_text(text)?,
-                _ => return Err(Error::NullElement),
-            }
-        }
+        let layout = if self.debug {
+            Some(LayoutTree::new(&self.window, &self.tree))
+        } else {
+            None
+        };
 
-        if !self.is_container {
-            self.font_context.clear();
+        let tree = std::mem::take(&mut self.tree);
+        self.paint_tree(tree)?;
+
+        if let Some(layout) = layout {
+            self.paint_debug(&layout)?;
         }
 
         Ok(())
@@ -201,8 +286,66 @@ impl OGImageWriter {
         self.context.encode(format)
     }
 
+    // Paint elements in the order they were set. Element with greater `z_index` is painted over.
+    fn paint_tree(&mut self, tree: Tree) -> Result<(), Error> {
+        let mut elements = tree.0;
+        // Sort is stable, so elements with the same `z_index` keep the order.
+        elements.sort_by_key(|elm| elm.style().map_or(0, |style| style.z_index));
+        for elm in elements {
+            match elm {
+                Element::Img(Some(img)) => self.paint_img(img)?,
+                Element::Text(Some(text)) => self.paint_text(text)?,
+                Element::Container(Some(container)) => self.paint_container(container)?,
+                _ => return Err(Error::NullElement),
+            }
+        }
+        Ok(())
+    }
+
+    fn paint_container(&mut self, mut container: Container) -> Result<(), Error> {
+        self.paint_decoration(&container.style, &container.rect)?;
+        if let Some((background, rect)) = container.take_background() {
+            self.context.draw_image(background, rect.x, rect.y)?;
+        }
+        match container.window.overflow {
+            Overflow::Visible => self.paint_tree(container.tree),
+            Overflow::Hidden => {
+                let style = &container.style;
+                let padding_box =
+                    Shape::new(&container.rect, &style.border_radius, style.border_width);
+                self.context.push_clip(padding_box);
+                let result = self.paint_tree(container.tree);
+                self.context.pop_clip();
+                result
+            }
+        }
+    }
+
     fn paint_img(&mut self, img: Img) -> Result<(), Error> {
-        self.context.draw_image(img.buf, img.rect.x, img.rect.y)
+        self.paint_decoration(&img.style, &img.rect)?;
+        // Image is stretched when its size is changed by style or flex, unless `object_fit` is specified.
+        let rect = img.content_rect();
+        let mut buf = match img.style.object_fit {
+            Some(object_fit) => fit(
+                img.buf,
+                object_fit,
+                &img.style.object_position,
+                rect.width,
+                rect.height,
+            ),
+            None => resize_exact(img.buf, rect.width, rect.height),
+        };
+        // Image is clipped by the curve of the content box.
+        round(
+            &mut buf,
+            &mut inner_radius(
+                &img.style.border_radius,
+                &img.style.inset(),
+                rect.width,
+                rect.height,
+            ),
+        );
+        self.context.draw_image(buf, rect.x, rect.y)
     }
 
     fn paint_text(&mut self, text_elm: Text) -> Result<(), Error> {
@@ -226,7 +369,7 @@ impl OGImageWriter {
 
             context.draw_text(
                 style.color.as_image_rgba(),
-                fragment.rect.x + *current_width,
+                fragment.rect.x + *current_width as i32,
                 fragment.rect.y,
                 font,
                 &setting,
@@ -239,6 +382,9 @@ impl OGImageWriter {
             Ok(())
         }
 
+        let rect = text_elm.drawn_rect().expand(&text_elm.style.inset());
+        self.paint_decoration(&text_elm.style, &rect)?;
+
         let style = text_elm.style;
         let mut current_split_text: Option<&SplitText> = None;
         let mut current_glyph: Option<&Glyph> = None;
@@ -284,7 +430,7 @@ impl OGImageWriter {
                         Some(glyph) => match &glyph.font_index_store {
                             FontIndexStore::Global(idx) => {
                                 let context = &mut self.context;
-                                self.font_context.with(idx, |font| {
+                                text_elm.font_context.with(idx, |font| {
                                     render_text(
                                         text,
                                         &mut range,
@@ -294,7 +440,7 @@ impl OGImageWriter {
                                         style,
                                         fragment,
                                     )
-                                })?;
+                                })??;
                             }
                             FontIndexStore::Parent(_) => {
                                 let font = match &text_elm.font {
@@ -348,7 +494,7 @@ impl OGImageWriter {
                     Some(glyph) => match &glyph.font_index_store {
                         FontIndexStore::Global(idx) => {
                             let context = &mut self.context;
-                            self.font_context.with(idx, |font| {
+                            text_elm.font_context.with(idx, |font| {
                                 render_text(
                                     text,
                                     &mut range,
@@ -358,7 +504,7 @@ impl OGImageWriter {
                                     style,
                                     fragment,
                                 )
-                            })?;
+                            })??;
                         }
                         FontIndexStore::Parent(_) => {
                             let font = match &text_elm.font {
@@ -401,3 +547,206 @@ impl OGImageWriter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{
+        AlignItems, BorderRadius, JustifyContent, ObjectFit, Padding, Position, Rgba,
+    };
+
+    fn paint_squares(z_index: i32) -> RgbaImage {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(100),
+            height: Some(100),
+            justify_content: JustifyContent::End,
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        for (color, left, z_index) in [([255, 0, 0, 255], 10, z_index), ([0, 0, 255, 255], 40, 0)] {
+            let mut square = OGImageWriter::new(WindowStyle {
+                width: Some(50),
+                height: Some(50),
+                background_color: Some(Rgba(color)),
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            writer
+                .set_container(
+                    &mut square,
+                    Style {
+                        position: Position::Absolute,
+                        top: Some(10),
+                        left: Some(left),
+                        z_index,
+                        ..Style::default()
+                    },
+                )
+                .unwrap();
+        }
+        writer.paint().unwrap();
+        writer.into_rgba().unwrap()
+    }
+
+    fn paint_object_fit(object_fit: ObjectFit) -> RgbaImage {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            width: Some(50),
+            height: Some(100),
+            background_color: Some(Rgba([255, 0, 0, 255])),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer
+            .set_img_with_data(
+                include_bytes!("../../assets/thumbnail_circle.png"),
+                50,
+                100,
+                ImageInputFormat::Png,
+                Style {
+                    object_fit: Some(object_fit),
+                    ..Style::default()
+                },
+            )
+            .unwrap();
+        writer.paint().unwrap();
+        writer.into_rgba().unwrap()
+    }
+
+    #[test]
+    fn test_paint_object_fit() {
+        // Square image is centered, and the rest of the box is transparent.
+        let contain = paint_object_fit(ObjectFit::Contain);
+        assert_eq!(contain.get_pixel(25, 5).0, [255, 0, 0, 255]);
+        assert_ne!(contain.get_pixel(25, 50).0, [255, 0, 0, 255]);
+        // Image covers the whole box.
+        let cover = paint_object_fit(ObjectFit::Cover);
+        assert_ne!(cover.get_pixel(25, 5).0, [255, 0, 0, 255]);
+        assert_ne!(cover.get_pixel(25, 95).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_paint_z_index() {
+        // Element that is set later is painted over.
+        assert_eq!(paint_squares(0).get_pixel(50, 30).0, [0, 0, 255, 255]);
+        assert_eq!(paint_squares(1).get_pixel(50, 30).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_paint_overflow() {
+        let paint = |overflow: Overflow| {
+            let mut writer = OGImageWriter::new(WindowStyle {
+                width: Some(100),
+                height: Some(100),
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            let mut child = OGImageWriter::new(WindowStyle {
+                width: Some(80),
+                height: Some(80),
+                background_color: Some(Rgba([255, 0, 0, 255])),
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            let mut container = OGImageWriter::new(WindowStyle {
+                width: Some(50),
+                height: Some(50),
+                overflow,
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            container
+                .set_container(&mut child, Style::default())
+                .unwrap();
+            writer
+                .set_container(
+                    &mut container,
+                    Style {
+                        border_radius: BorderRadius(20, 20, 20, 20),
+                        ..Style::default()
+                    },
+                )
+                .unwrap();
+            writer.paint().unwrap();
+            writer.into_rgba().unwrap()
+        };
+
+        let image = paint(Overflow::Visible);
+        assert_eq!(image.get_pixel(70, 10).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+
+        // Children are clipped by the container with its border radius.
+        let image = paint(Overflow::Hidden);
+        assert_eq!(image.get_pixel(70, 10).0[3], 0);
+        assert_eq!(image.get_pixel(0, 0).0[3], 0);
+        assert_eq!(image.get_pixel(25, 25).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_paint_text_background() {
+        let paint = |align_items| {
+            let mut writer = OGImageWriter::new(WindowStyle {
+                width: Some(600),
+                height: Some(300),
+                align_items,
+                justify_content: JustifyContent::Center,
+                ..WindowStyle::default()
+            })
+            .unwrap();
+            writer
+                .set_text(
+                    "Hello gjpqy World",
+                    Style {
+                        font_size: 60.,
+                        color: Rgba([0, 0, 255, 255]),
+                        background_color: Some(Rgba([255, 0, 0, 255])),
+                        ..Style::default()
+                    },
+                    Some(include_bytes!("../../fonts/Roboto-Light.ttf").to_vec()),
+                )
+                .unwrap();
+            writer.paint().unwrap();
+            writer.into_rgba().unwrap()
+        };
+
+        for align_items in [AlignItems::Start, AlignItems::Center] {
+            let image = paint(align_items);
+            let rows = |is_color: &dyn Fn([u8; 4]) -> bool| {
+                let rows = image.enumerate_pixels().filter(|(_, _, p)| is_color(p.0));
+                rows.map(|(_, y, _)| y).max().unwrap()
+            };
+            // Background covers the descenders of the last line.
+            let glyph_bottom = rows(&|p| p[2] > 128);
+            let background_bottom = rows(&|p| p == [255, 0, 0, 255]);
+            assert!(background_bottom >= glyph_bottom);
+        }
+    }
+
+    #[test]
+    fn test_paint_auto_size() {
+        let mut writer = OGImageWriter::new(WindowStyle {
+            height: Some(100),
+            min_width: Some(60),
+            background_color: Some(Rgba([0, 0, 255, 255])),
+            padding: Padding(10, 10, 10, 10),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        let mut square = OGImageWriter::new(WindowStyle {
+            width: Some(50),
+            height: Some(50),
+            background_color: Some(Rgba([255, 0, 0, 255])),
+            ..WindowStyle::default()
+        })
+        .unwrap();
+        writer.set_container(&mut square, Style::default()).unwrap();
+        assert_eq!((writer.width(), writer.height()), (0, 100));
+
+        // Image is allocated in the width of content and padding.
+        writer.paint().unwrap();
+        assert_eq!((writer.width(), writer.height()), (70, 100));
+        let image = writer.into_rgba().unwrap();
+        assert_eq!(image.dimensions(), (70, 100));
+        assert_eq!(image.get_pixel(5, 5).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(30, 30).0, [255, 0, 0, 255]);
+    }
+}
@@ -1,8 +1,8 @@
 mod style;
 
 use og_image_writer::{
-    font_context::FontContext, style::Style, writer::OGImageWriter, Error, ImageOutputFormat,
-    TextArea,
+    font_context::FontContext, measure::TextMeasurement, style::Style, writer::OGImageWriter,
+    Error, ImageOutputFormat, TextArea,
 };
 use std::panic;
 use std::path::Path;
@@ -64,11 +64,25 @@ impl JsFontContext {
         }
     }
 
-    pub fn push(&mut self, font: Vec<u8>) {
+    pub fn push(&self, font: Vec<u8>) {
         self.context.push(font).unwrap();
     }
 
-    pub fn clear(&mut self) {
+    pub fn push_with_index(&self, font: Vec<u8>, index: u32) {
+        self.context.push_with_index(font, index).unwrap();
+    }
+
+    pub fn register(&self, family: String, font: Vec<u8>) {
+        self.context.register(&family, font).unwrap();
+    }
+
+    pub fn register_with_index(&self, family: String, font: Vec<u8>, index: u32) {
+        self.context
+            .register_with_index(&family, font, index)
+            .unwrap();
+    }
+
+    pub fn clear(&self) {
         self.context.clear();
     }
 
@@ -85,6 +99,7 @@ struct JsSplitText {
     text: String,
     style: Option<Style>,
     font: Option<Vec<u8>>,
+    font_index: u32,
 }
 
 #[wasm_bindgen(start)]
@@ -105,15 +120,36 @@ impl JsTextArea {
 
     pub fn push(&mut self, text: String, style: Option<JsStyle>, font: Option<Vec<u8>>) {
         let style = style.map(from_js_style);
-        self.0.push(JsSplitText { text, style, font });
+        self.0.push(JsSplitText {
+            text,
+            style,
+            font,
+            font_index: 0,
+        });
+    }
+
+    pub fn push_with_index(&mut self, text: String, style: JsStyle, font: Vec<u8>, index: u32) {
+        self.0.push(JsSplitText {
+            text,
+            style: Some(from_js_style(style)),
+            font: Some(font),
+            font_index: index,
+        });
     }
 
     fn into_textarea(self) -> Result<TextArea, Error> {
         let mut textarea = TextArea::new();
         for mut split_text in self.0 {
             if let Some(style) = split_text.style.take() {
-                let font = split_text.font.take();
-                textarea.push(&split_text.text, style, font)?;
+                match split_text.font.take() {
+                    Some(font) => textarea.push_with_index(
+                        &split_text.text,
+                        style,
+                        font,
+                        split_text.font_index,
+                    )?,
+                    None => textarea.push(&split_text.text, style, None)?,
+                }
             } else {
                 textarea.push_text(&split_text.text);
             }
@@ -122,6 +158,27 @@ impl JsTextArea {
     }
 }
 
+#[wasm_bindgen(js_name = TextMeasurement)]
+pub struct JsTextMeasurement {
+    pub width: f32,
+    pub height: f32,
+    pub line_height: f32,
+    pub line_count: usize,
+    pub is_overflow: bool,
+}
+
+impl From<TextMeasurement> for JsTextMeasurement {
+    fn from(measurement: TextMeasurement) -> Self {
+        JsTextMeasurement {
+            width: measurement.width,
+            height: measurement.height,
+            line_height: measurement.line_height,
+            line_count: measurement.lines.len(),
+            is_overflow: measurement.is_overflow,
+        }
+    }
+}
+
 #[wasm_bindgen(js_name = OGImageWriter)]
 pub struct JsOGImageWriter {
     writer: OGImageWriter,
@@ -153,6 +210,40 @@ impl JsOGImageWriter {
         self.writer.height()
     }
 
+    pub fn set_debug(&mut self, debug: bool) {
+        self.writer.set_debug(debug);
+    }
+
+    pub fn set_font_context(&mut self, context: &JsFontContext) {
+        self.writer.set_font_context(&context.context);
+    }
+
+    pub fn measure_text(
+        &self,
+        text: String,
+        style: JsStyle,
+        font: Option<Vec<u8>>,
+    ) -> JsTextMeasurement {
+        let style = from_js_style(style);
+        self.writer
+            .measure_text(&text, &style, font)
+            .unwrap()
+            .into()
+    }
+
+    pub fn measure_textarea(
+        &self,
+        textarea: JsTextArea,
+        style: JsStyle,
+        font: Option<Vec<u8>>,
+    ) -> JsTextMeasurement {
+        let style = from_js_style(style);
+        self.writer
+            .measure_textarea(&textarea.into_textarea().unwrap(), &style, font)
+            .unwrap()
+            .into()
+    }
+
     pub fn set_text(&mut self, text: String, style: JsStyle, font: Option<Vec<u8>>) {
         let style = from_js_style(style);
         self.writer.set_text(&text, style, font).unwrap();
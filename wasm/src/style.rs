@@ -1,6 +1,7 @@
 use og_image_writer::style::{
-    AlignItems, BorderRadius, FlexDirection, JustifyContent, KernSetting, Margin, Position, Rgba,
-    Style, TextAlign, TextOverflow, WhiteSpace, WindowStyle, WordBreak,
+    AlignContent, AlignItems, BorderRadius, BorderStyle, BoxShadow, BoxSizing, FlexDirection,
+    FlexWrap, FontStyle, JustifyContent, KernSetting, Margin, ObjectFit, ObjectPosition, Overflow,
+    Padding, Position, Rgba, Style, TextAlign, TextOverflow, WhiteSpace, WindowStyle, WordBreak,
 };
 use std::marker::Copy;
 use wasm_bindgen::prelude::*;
@@ -74,9 +75,88 @@ impl JsMargin {
     }
 }
 
+#[wasm_bindgen(js_name = Padding)]
+#[derive(Default, Copy, Clone)]
+pub struct JsPadding {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+#[wasm_bindgen(js_class = Padding)]
+impl JsPadding {
+    pub fn new(top: u32, right: u32, bottom: u32, left: u32) -> JsPadding {
+        JsPadding {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+}
+
+#[wasm_bindgen(js_name = BoxShadow)]
+#[derive(Copy, Clone)]
+pub struct JsBoxShadow {
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub blur: u32,
+    pub spread: i32,
+    pub color: JsRgba,
+    pub inset: bool,
+}
+
+#[wasm_bindgen(js_class = BoxShadow)]
+impl JsBoxShadow {
+    pub fn new(
+        offset_x: i32,
+        offset_y: i32,
+        blur: u32,
+        spread: i32,
+        color: JsRgba,
+        inset: bool,
+    ) -> JsBoxShadow {
+        JsBoxShadow {
+            offset_x,
+            offset_y,
+            blur,
+            spread,
+            color,
+            inset,
+        }
+    }
+}
+
+#[wasm_bindgen(js_name = ObjectPosition)]
+#[derive(Copy, Clone)]
+pub struct JsObjectPosition {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[wasm_bindgen(js_class = ObjectPosition)]
+impl JsObjectPosition {
+    pub fn new(x: f32, y: f32) -> JsObjectPosition {
+        JsObjectPosition { x, y }
+    }
+}
+
+impl Default for JsObjectPosition {
+    fn default() -> Self {
+        JsObjectPosition { x: 0.5, y: 0.5 }
+    }
+}
+
 #[wasm_bindgen(js_name = Style)]
 pub struct JsStyle {
     pub margin: JsMargin,
+    pub padding: JsPadding,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub min_width: Option<u32>,
+    pub min_height: Option<u32>,
+    pub box_sizing: BoxSizing,
     /// For Text element
     pub line_height: f32,
     /// For Text element
@@ -91,21 +171,43 @@ pub struct JsStyle {
     /// For Text element
     pub color: JsRgba,
     /// For Text element
-    pub text_align: TextAlign,
+    /// Comma separated family names like CSS `font-family`.
+    font_family: String,
     /// For Text element
-    pub max_height: Option<u32>,
+    pub font_weight: u16,
+    /// For Text element
+    pub font_style: FontStyle,
     /// For Text element
+    pub text_align: TextAlign,
+    pub max_height: Option<u32>,
     pub max_width: Option<u32>,
     /// For Text element
     /// This property support multiline.
     text_overflow: String,
+    /// For Img element
+    pub object_fit: Option<ObjectFit>,
+    /// For Img element
+    pub object_position: JsObjectPosition,
     pub position: Position,
     pub top: Option<i32>,
     pub right: Option<i32>,
     pub bottom: Option<i32>,
     pub left: Option<i32>,
-    /// For Img element
+    pub ignore_window_padding: bool,
+    /// Radius of the border box. Image is also clipped by this.
     pub border_radius: JsBorderRadius,
+    /// Color painted behind the padding box and border of the element.
+    pub background_color: Option<JsRgba>,
+    /// Border is painted when this is greater than `0`.
+    pub border_width: u32,
+    pub border_color: JsRgba,
+    pub border_style: BorderStyle,
+    pub box_shadow: Option<JsBoxShadow>,
+    pub z_index: i32,
+    pub align_self: Option<AlignItems>,
+    pub flex_grow: f32,
+    pub flex_shrink: f32,
+    pub flex_basis: Option<u32>,
 }
 
 #[wasm_bindgen(js_class = Style)]
@@ -114,6 +216,11 @@ impl JsStyle {
         JsStyle::default()
     }
 
+    #[wasm_bindgen(setter)]
+    pub fn set_font_family(&mut self, value: String) {
+        self.font_family = value;
+    }
+
     #[wasm_bindgen(setter)]
     pub fn set_text_overflow(&mut self, value: JsTextOverflow) {
         self.text_overflow = value.into_serde().unwrap();
@@ -124,6 +231,12 @@ impl Default for JsStyle {
     fn default() -> Self {
         JsStyle {
             margin: JsMargin::default(),
+            padding: JsPadding::default(),
+            width: None,
+            height: None,
+            min_width: None,
+            min_height: None,
+            box_sizing: BoxSizing::ContentBox,
             line_height: 1.5,
             font_size: 30.,
             letter_spacing: 0,
@@ -136,29 +249,61 @@ impl Default for JsStyle {
                 b: 0,
                 a: 255,
             },
+            font_family: String::new(),
+            font_weight: 400,
+            font_style: FontStyle::Normal,
             text_align: TextAlign::Start,
             max_height: None,
             max_width: None,
             text_overflow: "clip".to_string(),
+            object_fit: None,
+            object_position: JsObjectPosition::default(),
             position: Position::Static,
             top: None,
             right: None,
             bottom: None,
             left: None,
+            ignore_window_padding: false,
             border_radius: JsBorderRadius::default(),
+            background_color: None,
+            border_width: 0,
+            border_color: JsRgba {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            border_style: BorderStyle::Solid,
+            box_shadow: None,
+            z_index: 0,
+            align_self: None,
+            flex_grow: 0.,
+            flex_shrink: 0.,
+            flex_basis: None,
         }
     }
 }
 
 #[wasm_bindgen(js_name = WindowStyle)]
 pub struct JsWindowStyle {
-    pub height: u32,
-    pub width: u32,
+    pub height: Option<u32>,
+    pub width: Option<u32>,
+    pub min_width: Option<u32>,
+    pub max_width: Option<u32>,
+    pub min_height: Option<u32>,
+    pub max_height: Option<u32>,
     pub background_color: Option<JsRgba>,
     pub align_items: AlignItems,
     pub justify_content: JustifyContent,
     /// This controls the direction in which the children of a node are laid out.
     pub flex_direction: FlexDirection,
+    pub flex_wrap: FlexWrap,
+    pub align_content: AlignContent,
+    pub gap: u32,
+    pub row_gap: Option<u32>,
+    pub column_gap: Option<u32>,
+    pub overflow: Overflow,
+    pub padding: JsPadding,
 }
 
 #[wasm_bindgen(js_class = WindowStyle)]
@@ -171,12 +316,23 @@ impl JsWindowStyle {
 impl Default for JsWindowStyle {
     fn default() -> Self {
         JsWindowStyle {
-            height: 0,
-            width: 0,
+            height: None,
+            width: None,
+            min_width: None,
+            max_width: None,
+            min_height: None,
+            max_height: None,
             background_color: None,
             align_items: AlignItems::Start,
             justify_content: JustifyContent::Start,
             flex_direction: FlexDirection::Column,
+            flex_wrap: FlexWrap::NoWrap,
+            align_content: AlignContent::Stretch,
+            gap: 0,
+            row_gap: None,
+            column_gap: None,
+            overflow: Overflow::Visible,
+            padding: JsPadding::default(),
         }
     }
 }
@@ -189,6 +345,17 @@ pub fn from_js_style(style: JsStyle) -> Style {
             style.margin.bottom,
             style.margin.left,
         ),
+        padding: Padding(
+            style.padding.top,
+            style.padding.right,
+            style.padding.bottom,
+            style.padding.left,
+        ),
+        width: style.width,
+        height: style.height,
+        min_width: style.min_width,
+        min_height: style.min_height,
+        box_sizing: style.box_sizing,
         line_height: style.line_height,
         font_size: style.font_size,
         letter_spacing: style.letter_spacing,
@@ -196,6 +363,19 @@ pub fn from_js_style(style: JsStyle) -> Style {
         word_break: style.word_break,
         white_space: style.white_space,
         color: Rgba([style.color.r, style.color.g, style.color.b, style.color.a]),
+        font_family: style
+            .font_family
+            .split(',')
+            .map(|family| {
+                family
+                    .trim()
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_string()
+            })
+            .filter(|family| !family.is_empty())
+            .collect(),
+        font_weight: style.font_weight,
+        font_style: style.font_style,
         text_align: style.text_align,
         max_height: style.max_height,
         max_width: style.max_width,
@@ -206,17 +386,49 @@ pub fn from_js_style(style: JsStyle) -> Style {
                 _ => TextOverflow::Content(style.text_overflow.clone()),
             }
         },
+        object_fit: style.object_fit,
+        object_position: ObjectPosition(style.object_position.x, style.object_position.y),
         position: style.position,
         top: style.top,
         right: style.right,
         bottom: style.bottom,
         left: style.left,
+        ignore_window_padding: style.ignore_window_padding,
         border_radius: BorderRadius(
             style.border_radius.top_left,
             style.border_radius.top_right,
             style.border_radius.bottom_left,
             style.border_radius.bottom_right,
         ),
+        background_color: style
+            .background_color
+            .map(|color| Rgba([color.r, color.g, color.b, color.a])),
+        border_width: style.border_width,
+        border_color: Rgba([
+            style.border_color.r,
+            style.border_color.g,
+            style.border_color.b,
+            style.border_color.a,
+        ]),
+        border_style: style.border_style,
+        box_shadow: style.box_shadow.map(|shadow| BoxShadow {
+            offset_x: shadow.offset_x,
+            offset_y: shadow.offset_y,
+            blur: shadow.blur,
+            spread: shadow.spread,
+            color: Rgba([
+                shadow.color.r,
+                shadow.color.g,
+                shadow.color.b,
+                shadow.color.a,
+            ]),
+            inset: shadow.inset,
+        }),
+        z_index: style.z_index,
+        align_self: style.align_self,
+        flex_grow: style.flex_grow,
+        flex_shrink: style.flex_shrink,
+        flex_basis: style.flex_basis,
     }
 }
 
@@ -224,11 +436,27 @@ pub fn from_js_window_style(style: JsWindowStyle) -> WindowStyle {
     WindowStyle {
         height: style.height,
         width: style.width,
+        min_width: style.min_width,
+        max_width: style.max_width,
+        min_height: style.min_height,
+        max_height: style.max_height,
         background_color: style
             .background_color
             .map(|color| Rgba([color.r, color.g, color.b, color.a])),
         align_items: style.align_items,
         justify_content: style.justify_content,
         flex_direction: style.flex_direction,
+        flex_wrap: style.flex_wrap,
+        align_content: style.align_content,
+        gap: style.gap,
+        row_gap: style.row_gap,
+        column_gap: style.column_gap,
+        overflow: style.overflow,
+        padding: Padding(
+            style.padding.top,
+            style.padding.right,
+            style.padding.bottom,
+            style.padding.left,
+        ),
     }
 }